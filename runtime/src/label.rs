@@ -0,0 +1,105 @@
+use core::{
+    fmt,
+    marker::PhantomData,
+};
+
+use serde::{
+    de::{
+        Error,
+        Unexpected,
+        Visitor,
+    },
+    Deserializer,
+};
+
+/// Returns the position of the first label equal to the given string.
+///
+/// Derived enums store their spellings in a static table and map the position back to a variant,
+/// so the comparison loop is shared by every enum rather than generated for each one.
+pub fn position(labels: &[&str], s: &str) -> Option<usize> {
+    labels.iter().position(|label| *label == s)
+}
+
+/// Returns the position of the first label equal to the given string under the given comparison.
+///
+/// Derived enums matching strings case-insensitively pass a comparison folding case, so that the
+/// loop is still shared while each enum keeps its own folding.
+pub fn position_by(labels: &[&str], s: &str, eq: fn(&str, &str) -> bool) -> Option<usize> {
+    labels.iter().position(|label| eq(label, s))
+}
+
+/// A labeled enum that can be parsed from one of its labels.
+///
+/// Implemented by `DeserializeLabeledStringEnum` when the `runtime` feature of serde_string_enum
+/// is enabled, for enums whose deserialization only depends on their labels.
+pub trait FromLabel: Sized {
+    /// The name of the enum, used in error messages.
+    const NAME: &'static str;
+
+    /// Returns the variant labeled with the given string, if any.
+    fn from_label(s: &str) -> Option<Self>;
+
+    /// Returns the label closest to a string that matches no label, to suggest in errors.
+    fn suggest(s: &str) -> Option<&'static str> {
+        let _ = s;
+        None
+    }
+}
+
+/// Visitor that deserializes a [`FromLabel`] type from a string.
+pub struct LabelVisitor<T> {
+    marker: PhantomData<T>,
+}
+
+impl<T> LabelVisitor<T> {
+    /// Creates a new visitor.
+    pub const fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for LabelVisitor<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'de, T> Visitor<'de> for LabelVisitor<T>
+where
+    T: FromLabel,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a valid {} string value", T::NAME)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match T::from_label(v) {
+            Some(value) => Ok(value),
+            None => match T::suggest(v) {
+                Some(suggestion) => Err(E::custom(format_args!(
+                    "unknown {} {:?}, did you mean {:?}?",
+                    T::NAME,
+                    v,
+                    suggestion
+                ))),
+                None => Err(E::invalid_value(Unexpected::Str(v), &self)),
+            },
+        }
+    }
+}
+
+/// Deserializes a [`FromLabel`] type from a string.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromLabel,
+{
+    deserializer.deserialize_str(LabelVisitor::new())
+}
@@ -0,0 +1,29 @@
+//! Runtime support types for enums derived with
+//! [serde_string_enum](https://docs.rs/serde_string_enum).
+//!
+//! The [`label`] module holds the lookup and deserialization logic shared by derived enums when the
+//! `runtime` feature of serde_string_enum is enabled, which also implements [`StringEnum`] and
+//! describes each enum with an [`EnumMeta`]. With the `std` feature, the [`registry`] module
+//! collects [`StringEnum`] types by name.
+
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod label;
+mod meta;
+#[cfg(feature = "std")]
+pub mod registry;
+mod string_enum;
+mod tagged;
+
+pub use meta::{
+    EnumMeta,
+    VariantMeta,
+};
+pub use string_enum::StringEnum;
+pub use tagged::{
+    Prefix,
+    Tagged,
+};
@@ -0,0 +1,24 @@
+/// A description of a labeled enum, generated as the `META` constant of enums deriving
+/// `DeserializeLabeledStringEnum` when the `runtime` feature of serde_string_enum is enabled.
+///
+/// Documentation generators and schema exporters can read it to list the strings an enum accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnumMeta {
+    /// The name of the enum.
+    pub name: &'static str,
+    /// Every variant, in declaration order.
+    pub variants: &'static [VariantMeta],
+    /// Whether labels are matched exactly, rather than regardless of case.
+    pub case_sensitive: bool,
+}
+
+/// A description of one variant of a labeled enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariantMeta {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The label of the variant, or `None` if it holds the string it was parsed from.
+    pub label: Option<&'static str>,
+    /// The aliases also parsed as the variant.
+    pub aliases: &'static [&'static str],
+}
@@ -0,0 +1,59 @@
+//! A registry of [`StringEnum`] types, keyed by type name, for reflection over every enum an
+//! application has registered.
+
+use std::{
+    boxed::Box,
+    collections::BTreeMap,
+    sync::{
+        PoisonError,
+        RwLock,
+    },
+    vec::Vec,
+};
+
+use crate::StringEnum;
+
+/// The labeled variants of a registered enum.
+pub type Variants = &'static [&'static (dyn StringEnum + Sync)];
+
+static REGISTRY: RwLock<BTreeMap<&'static str, Variants>> = RwLock::new(BTreeMap::new());
+
+/// Registers `T` under its [`core::any::type_name`], returning its variants.
+///
+/// Registering a type more than once has no further effect.
+pub fn register<T>() -> Variants
+where
+    T: StringEnum + Sync + 'static,
+{
+    let type_name = core::any::type_name::<T>();
+    let mut registry = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+    if let Some(variants) = registry.get(type_name) {
+        return variants;
+    }
+    let variants = T::variants()
+        .iter()
+        .map(|variant| variant as &'static (dyn StringEnum + Sync))
+        .collect::<Vec<_>>();
+    let variants = Box::leak(variants.into_boxed_slice());
+    registry.insert(type_name, variants);
+    variants
+}
+
+/// Returns the variants of the enum registered under the given type name, if any.
+pub fn get(type_name: &str) -> Option<Variants> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(type_name)
+        .copied()
+}
+
+/// Returns the names of every registered type, in sorted order.
+pub fn type_names() -> Vec<&'static str> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .keys()
+        .copied()
+        .collect()
+}
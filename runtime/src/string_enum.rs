@@ -0,0 +1,24 @@
+/// Metadata about a labeled enum, usable through `dyn StringEnum` by code that works over any
+/// derived enum, such as admin interfaces and generic validators.
+///
+/// Implemented by `DeserializeLabeledStringEnum` for enums without generic parameters when the
+/// `runtime` feature of serde_string_enum is enabled.
+pub trait StringEnum {
+    /// Returns the label of the variant, or the string held by an `other` or `pattern` variant.
+    fn label(&self) -> &str;
+
+    /// Returns the aliases also parsed as the variant.
+    fn aliases(&self) -> &'static [&'static str];
+
+    /// Returns the variant labeled with the given string, if any.
+    fn from_label(s: &str) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Returns every labeled variant, in declaration order.
+    ///
+    /// Variants holding the string they were parsed from are not included.
+    fn variants() -> &'static [Self]
+    where
+        Self: Sized;
+}
@@ -0,0 +1,427 @@
+use core::{
+    fmt::{
+        self,
+        Write,
+    },
+    marker::PhantomData,
+};
+
+use serde::{
+    de::{
+        value::{
+            BorrowedStrDeserializer,
+            StrDeserializer,
+        },
+        Error,
+        Unexpected,
+        Visitor,
+    },
+    ser::{
+        self,
+        Impossible,
+    },
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+
+/// A marker type carrying the prefix written by [`Tagged`].
+///
+/// ```
+/// use serde_string_enum_runtime::Prefix;
+///
+/// struct Event;
+///
+/// impl Prefix for Event {
+///     const PREFIX: &'static str = "evt:";
+/// }
+/// ```
+pub trait Prefix {
+    const PREFIX: &'static str;
+}
+
+/// Wrapper that namespaces a string-encoded value with the prefix of `P`.
+///
+/// The wrapped value is serialized as the prefix followed by the string its [`Serialize`]
+/// implementation writes, so that transformations such as `serialize_case` still apply, and
+/// deserialized by stripping the prefix before handing the rest of the string to the value's
+/// [`Deserialize`] implementation. Strings without the prefix are rejected. Values that do not
+/// serialize as strings fail to serialize.
+///
+/// [`fmt::Display`] writes the prefix followed by the value's own output, padded as a whole.
+///
+/// This allows the same enum to appear as `"evt:Fire"` in one API and `"Fire"` in another.
+pub struct Tagged<T, P> {
+    value: T,
+    prefix: PhantomData<P>,
+}
+
+impl<T, P> Tagged<T, P> {
+    /// Wraps the given value.
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            prefix: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the wrapped value.
+    pub const fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, P> From<T> for Tagged<T, P> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, P> Clone for Tagged<T, P>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T, P> Copy for Tagged<T, P> where T: Copy {}
+
+impl<T, P> PartialEq for Tagged<T, P>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T, P> Eq for Tagged<T, P> where T: Eq {}
+
+impl<T, P> fmt::Debug for Tagged<T, P>
+where
+    T: fmt::Debug,
+    P: Prefix,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Tagged")
+            .field(&P::PREFIX)
+            .field(&self.value)
+            .finish()
+    }
+}
+
+impl<T, P> fmt::Display for Tagged<T, P>
+where
+    T: fmt::Display,
+    P: Prefix,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Width, fill, and alignment apply to the prefixed string rather than to the value alone.
+        let width = match f.width() {
+            Some(width) => width,
+            None => return write!(f, "{}{}", P::PREFIX, self.value),
+        };
+        let mut len = CharCount(P::PREFIX.chars().count());
+        write!(len, "{}", self.value)?;
+        let padding = width.saturating_sub(len.0);
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(fmt::Alignment::Left) | None => (0, padding),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        write!(f, "{}{}", P::PREFIX, self.value)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+/// Counts the characters written to it, for padding a [`Tagged`] value as a whole.
+struct CharCount(usize);
+
+impl Write for CharCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+impl<T, P> Serialize for Tagged<T, P>
+where
+    T: Serialize,
+    P: Prefix,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(PrefixedSerializer {
+            serializer,
+            prefix: PhantomData::<P>,
+        })
+    }
+}
+
+/// Serializer that writes the prefix of `P` before the string serialized by a value, and rejects
+/// anything but strings.
+struct PrefixedSerializer<S, P> {
+    serializer: S,
+    prefix: PhantomData<P>,
+}
+
+impl<S, P> PrefixedSerializer<S, P>
+where
+    S: Serializer,
+    P: Prefix,
+{
+    fn unsupported(&self) -> S::Error {
+        ser::Error::custom(format_args!(
+            "value tagged with {:?} must serialize as a string",
+            P::PREFIX
+        ))
+    }
+}
+
+impl<S, P> Serializer for PrefixedSerializer<S, P>
+where
+    S: Serializer,
+    P: Prefix,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Impossible<S::Ok, S::Error>;
+    type SerializeTuple = Impossible<S::Ok, S::Error>;
+    type SerializeTupleStruct = Impossible<S::Ok, S::Error>;
+    type SerializeTupleVariant = Impossible<S::Ok, S::Error>;
+    type SerializeMap = Impossible<S::Ok, S::Error>;
+    type SerializeStruct = Impossible<S::Ok, S::Error>;
+    type SerializeStructVariant = Impossible<S::Ok, S::Error>;
+
+    fn is_human_readable(&self) -> bool {
+        self.serializer.is_human_readable()
+    }
+
+    fn serialize_str(self, v: &str) -> Result<S::Ok, S::Error> {
+        self.collect_str(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<S::Ok, S::Error> {
+        self.collect_str(&v)
+    }
+
+    fn collect_str<T>(self, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: fmt::Display + ?Sized,
+    {
+        self.serializer
+            .collect_str(&format_args!("{}{}", P::PREFIX, value))
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _: bool) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_some<T>(self, _: &T) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(self.unsupported())
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(self.unsupported())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        Err(self.unsupported())
+    }
+}
+
+struct TaggedVisitor<T, P> {
+    marker: PhantomData<(T, P)>,
+}
+
+impl<'de, T, P> Visitor<'de> for TaggedVisitor<T, P>
+where
+    T: Deserialize<'de>,
+    P: Prefix,
+{
+    type Value = Tagged<T, P>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a string starting with {:?}", P::PREFIX)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let rest = v
+            .strip_prefix(P::PREFIX)
+            .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))?;
+        T::deserialize(StrDeserializer::new(rest)).map(Tagged::new)
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let rest = v
+            .strip_prefix(P::PREFIX)
+            .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))?;
+        T::deserialize(BorrowedStrDeserializer::new(rest)).map(Tagged::new)
+    }
+}
+
+impl<'de, T, P> Deserialize<'de> for Tagged<T, P>
+where
+    T: Deserialize<'de>,
+    P: Prefix,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TaggedVisitor {
+            marker: PhantomData,
+        })
+    }
+}
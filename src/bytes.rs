@@ -0,0 +1,188 @@
+//! Code generation for enums labeled with byte strings, which are serialized with
+//! `serialize_bytes` rather than as strings.
+
+use alloc::{
+    fmt::format,
+    vec::Vec,
+};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::Ident;
+
+use crate::{
+    constructor_of,
+    de_generics,
+    debug_expand,
+    description,
+    inline_hint,
+    label_bytes,
+    parse::LabeledStringInput,
+    variant_ids,
+};
+
+pub fn derive_serialize(input: LabeledStringInput) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let inline = inline_hint(&input.attrs);
+    let description = description(&input);
+    let label_bytes = label_bytes(&input);
+    let variant_ids = variant_ids(&input);
+
+    let match_labels = input.variants.iter().map(|variant| {
+        let pattern = variant.pattern();
+        let bytes = variant.attrs.string.as_ref().unwrap();
+        quote! {
+            Self::#pattern => serializer.serialize_bytes(#bytes),
+        }
+    });
+
+    debug_expand(
+        &input.attrs,
+        "SerializeLabeledStringEnum",
+        quote! {
+            impl #impl_generics ::serde::Serialize for #ident #ty_generics #where_clause {
+                #inline
+                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                    match self {
+                        #(#match_labels)*
+                    }
+                }
+            }
+
+            #description
+
+            #label_bytes
+
+            #variant_ids
+        },
+    )
+}
+
+pub fn derive_deserialize(input: LabeledStringInput) -> TokenStream {
+    let call_site = Span::call_site();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let de_generics = de_generics(&input.generics);
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+    let visitor_ident = Ident::new(&format(format_args!("{ident}Visitor")), call_site);
+    let inline = inline_hint(&input.attrs);
+
+    // Byte string patterns may not repeat, so only the first variant with a spelling matches it.
+    let mut seen = Vec::new();
+    let mut max_len = 0;
+    let arms = input
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            let spellings = variant
+                .attrs
+                .string
+                .iter()
+                .chain(&variant.attrs.aliases)
+                .filter_map(|label| label.as_bytes())
+                .filter(|bytes| {
+                    let value = bytes.value();
+                    max_len = max_len.max(value.len());
+                    if seen.contains(&value) {
+                        return false;
+                    }
+                    seen.push(value);
+                    true
+                })
+                .collect::<Vec<_>>();
+            if spellings.is_empty() {
+                return None;
+            }
+            let constructor = variant.constructor();
+            Some(quote! {
+                #(#spellings)|* => ::core::option::Option::Some(Self::#constructor),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let (bytes_fallback, seq_fallback) = match &input.attrs.default {
+        Some(default) => {
+            let default = constructor_of(&input.variants, default);
+            (
+                quote! { ::core::result::Result::Ok(#ident::#default) },
+                quote! { ::core::result::Result::Ok(#ident::#default) },
+            )
+        }
+        None => (
+            quote! { ::core::result::Result::Err(E::invalid_value(::serde::de::Unexpected::Bytes(v), &self)) },
+            quote! { ::core::result::Result::Err(<A::Error as ::serde::de::Error>::invalid_value(::serde::de::Unexpected::Seq, &self)) },
+        ),
+    };
+
+    let deserialize = quote! {
+        deserializer.deserialize_bytes(#visitor_ident { marker: ::core::marker::PhantomData })
+    };
+
+    debug_expand(
+        &input.attrs,
+        "DeserializeLabeledStringEnum",
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #inline
+                fn __from_bytes(v: &[u8]) -> ::core::option::Option<Self> {
+                    match v {
+                        #(#arms)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+
+            struct #visitor_ident #impl_generics #where_clause {
+                marker: ::core::marker::PhantomData<#ident #ty_generics>,
+            }
+
+            impl #de_impl_generics ::serde::de::Visitor<'de> for #visitor_ident #ty_generics #where_clause {
+                type Value = #ident #ty_generics;
+
+                fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    formatter.write_fmt(::core::format_args!("a valid {} byte string value", ::core::stringify!(#ident)))
+                }
+
+                #inline
+                fn visit_bytes<E>(self, v: &[u8]) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                    match Self::Value::__from_bytes(v) {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                        ::core::option::Option::None => #bytes_fallback,
+                    }
+                }
+
+                fn visit_str<E>(self, v: &str) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                    <Self as ::serde::de::Visitor<'de>>::visit_bytes(self, v.as_bytes())
+                }
+
+                // Formats without a native byte string type write bytes as a sequence.
+                fn visit_seq<A>(self, mut seq: A) -> ::core::result::Result<Self::Value, A::Error> where A: ::serde::de::SeqAccess<'de> {
+                    let mut buffer = [0u8; #max_len];
+                    let mut len = 0usize;
+                    while let ::core::option::Option::Some(byte) = seq.next_element::<u8>()? {
+                        if len < #max_len {
+                            buffer[len] = byte;
+                        }
+                        len += 1;
+                    }
+                    let value = if len <= #max_len {
+                        Self::Value::__from_bytes(&buffer[..len])
+                    } else {
+                        ::core::option::Option::None
+                    };
+                    match value {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                        ::core::option::Option::None => #seq_fallback,
+                    }
+                }
+            }
+
+            impl #de_impl_generics ::serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+                fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: ::serde::Deserializer<'de> {
+                    #deserialize
+                }
+            }
+        },
+    )
+}
@@ -99,221 +99,4288 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! # Labels
+//! Each variant is labeled with a `#[string = ...]` attribute, and may be given any number of
+//! `#[alias = ...]` attributes, which are accepted in addition to the label when deserializing.
+//!
+//! A label may also be a `&'static str` constant, given with the list form of the attribute, as
+//! in `#[string(LABEL)]`, or a macro invocation such as `#[string = concat!("Gr", "ass")]`.
+//! The list form is the only way to name a constant, since Rust rejects anything but literals and
+//! macro invocations after `=` in attributes. Aliases accept macro invocations as well.
+//!
+//! A label written as a character literal, as in `#[string = 'G']`, is a one-character string.
+//! When every label is a character literal, variants are serialized with `serialize_char`, unless
+//! the enum has an `other` variant or transforms its labels.
+//!
+//! Labels may instead be byte strings, as in `#[string = b"\x01GR"]`, for binary protocols. Every
+//! label and alias of the enum must then be a byte string. Variants are serialized with
+//! `serialize_bytes`, and deserialized with `deserialize_bytes`, also accepting strings and
+//! sequences of bytes. `Display` and `FromStr` are not implemented for such enums.
+//!
+//! A labeled variant may carry fields, which are ignored when serializing. Deserializing requires
+//! each to implement [`Default`], and parses the variant with its fields set to their default
+//! values, as do `from_index()` and `from_label_id()`, which are then not `const`.
+//!
+//! ## Variant Attributes
+//! - `#[string(other)]` - Catch all unknown strings in this variant, which must hold a single
+//!   string field. The field may borrow from the input (`&'de str` or `Cow<'de, str>`), in which
+//!   case no allocation takes place when the deserializer supports borrowing. `FromStr` is only
+//!   implemented when the field can be constructed from an owned `String`.
+//! - `#[string(pattern = "ERR_*")]` - Parse unknown strings matching the given glob as this
+//!   variant, which must hold a single owned string field storing the full string. In the glob, `*`
+//!   matches any run of characters and `?` matches a single character. Other characters are matched
+//!   under the same case folding as labels with the `unicase` feature. Patterns are tried in
+//!   declaration order, once no label has matched. Requires the `alloc` feature.
+//! - `#[string(pattern = "ERR_*", priority = 1)]` - Try this pattern before those of lower
+//!   priority, regardless of declaration order. Patterns have priority 0 by default.
+//! - `#[string(bool = true)]` - Also deserialize the given boolean value as this variant. Since
+//!   booleans cannot be told apart from strings without inspecting the input, the generated
+//!   implementation requires a self-describing format, such as JSON.
+//! - `#[number = N]` - Also deserialize the given non-negative integer as this variant, for example
+//!   when consuming older API versions that encoded the enum numerically. Like booleans, numbers
+//!   require a self-describing format.
+//!
+//! # Serializing
+//! [`macro@SerializeLabeledStringEnum`] also implements [`core::fmt::Display`], which writes the
+//! label and honors width, fill, alignment, and precision like it would for a string slice, and
+//! [`AsRef<str>`], which returns the label without allocating. A variant labeled `#[string("Fire",
+//! alt = "Fire Type")]` is instead written as its alternate text when formatted with `{:#}`, so
+//! that one enum serves both compact wire output and verbose human-readable output.
+//!
+//! Also generates `label_bytes()`, a `const fn` returning the label of each variant as bytes,
+//! `label_id()` and `from_label_id()`, which convert between variants and stable `u32` ids for
+//! storage and metrics that intern the enum, and `index()` and `from_index()`, which convert
+//! between variants and their positions for array-backed tables, unless the enum has an `other` or
+//! `pattern` variant. Ids follow declaration order unless given with `#[string(id = N)]`.
+//!
+//! A variant marked with `#[string(other)]` is serialized as the string it holds.
+//!
+//! With the `alloc` or `std` feature, the enum also converts into `Cow<'static, str>`, borrowing
+//! the label so that APIs taking `Into<Cow<'static, str>>` accept it without allocating. A string
+//! held by the `#[string(other)]` variant is moved into `Cow::Owned`.
+//!
+//! ## Container Attributes
+//! - `#[string_enum(allow_empty)]` - Allow a label or alias to be the empty string, which is
+//!   otherwise a compile error.
+//! - `#[string_enum(alias_switch)]` - Generate `serialize_as_alias(alias_index, serializer)`, which
+//!   serializes the alias at the given position instead of the label, so that a legacy spelling can
+//!   be emitted during a staged rollout. Aliases are cased and qualified as labels are. Variants
+//!   with fewer aliases are serialized as usual.
+//! - `#[string_enum(archive_as = "index")]` - Archive each variant as its index in declaration
+//!   order instead of its label in the implementations generated by `#[string_enum(rkyv)]`.
+//! - `#[string_enum(borrow_str)]` - Implement [`core::borrow::Borrow<str>`] returning the label, so
+//!   that a `HashSet` or `HashMap` keyed by the enum can be probed with a `&str`. `Borrow` requires
+//!   `Hash`, `Eq`, and `Ord` to agree with those of the label, which the derived implementations do
+//!   not, so they must be implemented by hand over the label.
+//! - `#[string_enum(debug_expand)]` - Display the code generated by each labeled derive as a
+//!   deprecation warning, for debugging without installing `cargo-expand`.
+//! - `#[string_enum(description)]` - Generate `description()`, which returns the doc comment of
+//!   each variant, for showing explanations alongside labels.
+//! - `#[string_enum(hash_by_label)]` - Implement [`core::hash::Hash`] over the label instead of the
+//!   discriminant, so that persisted hashes stay the same when variants are reordered. The hash
+//!   also agrees with that of the label as a `&str`, as `borrow_str` requires.
+//! - `#[string_enum(inline)]` - Mark the generated `Display`, `Serialize`, and parsing functions as
+//!   `#[inline]`, and construct parse errors in a separate `#[cold]` function.
+//! - `#[string_enum(label_constants)]` - Generate a constant holding the label of each labeled
+//!   variant, named after the variant in screaming snake case and suffixed with `_LABEL`, as in
+//!   `Type::GRASS_LABEL`, so that code elsewhere can refer to a label without repeating it. Two
+//!   variants whose names map to the same constant fail to compile.
+//! - `#[string_enum(label_from_doc)]` - Use the first line of each variant's doc comment as its
+//!   label when it has no `#[string = ...]` attribute.
+//! - `#[string_enum(label_policy = "kebab")]` - Fail to compile if a literal label or alias does
+//!   not follow the given convention: `"non_empty"` rejects empty labels and surrounding
+//!   whitespace, `"ascii"` additionally rejects non-printable and non-ASCII characters, and
+//!   `"kebab"` requires lowercase words separated by hyphens.
+//! - `#[string_enum(list_delimiter = ",")]` - Generate `format_list(values)`, which joins the
+//!   labels of the values with the delimiter into a `String`. Requires the `alloc` feature.
+//! - `#[string_enum(profile(legacy = 0))]` - Generate `serialize_with_profile(profile,
+//!   serializer)`, which serializes the alias at the position named by the profile, as with
+//!   `alias_switch`, and `with_profile(profile, f)`, which calls `f` with every serialization of
+//!   the enum on the current thread using the profile, to select between spellings per request. The
+//!   profile set by `with_profile` is shared by all instantiations of a generic enum and does not
+//!   follow a task to another thread. Requires the `std` feature.
+//! - `#[string_enum(qualified)]` - Prefix each serialized label with the enum name and `::`, as in
+//!   `"Type::Grass"`, to tell apart enums sharing a single column. A custom separator may be given
+//!   with `#[string_enum(qualified = ".")]`. The generated `Display` implementation still writes
+//!   the bare label, and an `other` variant serializes its string as is.
+//! - `#[string_enum(remote = "other_crate::Type")]` - Treat the enum as a mirror of the given
+//!   foreign enum, whose variants have the same names, and generate `serialize(value, serializer)`,
+//!   which serializes a value of the foreign enum as a label, so that fields of that type can use
+//!   `#[serde(with = "Enum")]`. Every variant must be a unit variant.
+//! - `#[string_enum(serde_as = TypeLabel)]` - Implement `serde_with::SerializeAs` for the given
+//!   type, usually a unit struct declared alongside the enum, instead of implementing `Serialize`
+//!   for the enum, so that the enum can keep another representation where fields do not opt into
+//!   labels with `#[serde_as(as = "TypeLabel")]`. Requires the `serde_with` feature.
+//! - `#[string_enum(serialize_as = "number")]` - Serialize each variant as its `#[number = N]`
+//!   instead of its label, which every variant must then have. The generated `Display`
+//!   implementation still writes the label.
+//! - `#[string_enum(serialize_case = "lowercase")]` - Transform labels to `"lowercase"` or
+//!   `"UPPERCASE"` when serializing. The generated `Display` implementation still writes the label
+//!   as written, and deserialization accepts both spellings.
+//! - `#[string_enum(subset_of = FullType)]` - Fail to compile unless each label of the enum is also
+//!   a label of the given enum, which must also derive this macro, so that a restricted copy of an
+//!   enum cannot drift out of sync with the original.
+//! - `#[string_enum(with_module = type_as_string)]` - Generate a module of the given name with
+//!   `serialize` and `deserialize` functions, for fields marked with `#[serde(with =
+//!   "type_as_string")]`, instead of implementing `Serialize` and `Deserialize`, so that the enum
+//!   can keep another representation everywhere else. The module is emitted by
+//!   [`macro@DeserializeLabeledStringEnum`], which must also be derived. Cannot be used with
+//!   generic enums or with `serde_as`.
+//!
+//! # Deserializing
+//! [`macro@DeserializeLabeledStringEnum`] also implements [`core::str::FromStr`], which fails with
+//! a generated `{Enum}ParseError` type that carries the offending input (or its length and prefix
+//! when no allocator is available), and converts into the `String` message that `from_str` failed
+//! with before 0.3.
+//!
+//! Also generates `from_bytes(bytes)`, which parses a byte buffer, such as a header value or a
+//! protocol frame, failing with the same error when the bytes are not valid UTF-8. The error then
+//! carries the input decoded lossily.
+//!
+//! With the `unicase` feature, strings are matched under full Unicode case folding, which may
+//! change the length of a string, so that a label of `"Straße"` also matches `"STRASSE"` and
+//! `"STRAẞE"`. `#[string_enum(case_fold = "simple")]` selects simple case folding instead.
+//!
+//! Unless unknown strings fall back to a `default` or `other` variant, also generates
+//! `parse_str(s)`, which fails with a generated `{Enum}BorrowedParseError<'a>` type borrowing the
+//! offending input, giving the full input without an allocator.
+//!
+//! Shares the `debug_expand`, `inline`, `label_from_doc`, `label_policy`, `qualified`, and
+//! `serialize_case` container attributes with [`macro@SerializeLabeledStringEnum`]. Qualified
+//! labels are accepted with or without their qualifier.
+//!
+//! Also generates `matches(s)`, which returns whether the string is a label or alias, for
+//! validators that only need a yes or no answer. Pattern variants, `catch_all_with`, and
+//! `on_unknown` are not consulted.
+//!
+//! Also generates `all_accepted_strings()`, which iterates over every label and alias, for
+//! validation patterns, documentation tables, and schemas that must include aliases. Labels changed
+//! by `serialize_case` are given in both spellings.
+//!
+//! With the `suggestions` feature, enums without a `default` or `other` variant also generate
+//! `closest_match(s)`, which parses the string or else the label or alias closest to it, for
+//! correcting near misses in interactive tools.
+//!
+//! With the `tracing` feature, parsing an unknown string as the `default` or `other` variant emits
+//! a `tracing` warning naming the enum and the string.
+//!
+//! With the `alias_registry` feature, `register_alias(alias, variant)` adds a spelling at runtime,
+//! which is accepted once no label or alias given at compile time has matched.
+//!
+//! With the `std` feature, enums without generic parameters or `other` and `pattern` variants also
+//! generate `label_map()`, which returns a `HashMap` from each exact spelling to its variant for
+//! bulk lookups.
+//!
+//! With the `runtime` feature, the enum is described by a generated `META` constant of type
+//! `serde_string_enum_runtime::EnumMeta`, and enums without generic parameters implement
+//! `serde_string_enum_runtime::StringEnum`.
+//!
+//! ## Container Attributes
+//! - `#[string_enum(allow_empty)]` - Allow a label or alias to be the empty string, which is
+//!   otherwise a compile error.
+//! - `#[string_enum(c_ffi = "mylib_")]` - Export `extern "C"` functions for C and C++ hosts, named
+//!   after the enum in snake case following the given prefix: `{prefix}{enum}_label(v)`, which
+//!   returns the NUL-terminated static label of the variant at the given index in declaration
+//!   order, or null, and `{prefix}{enum}_from_label(label)`, which parses a NUL-terminated string
+//!   and returns the index of the variant, or -1. Exported names must be unique across everything
+//!   linked into the final binary, so the prefix should name the library. Every variant must be a
+//!   unit variant with a string literal label.
+//! - `#[string_enum(case_fold = "simple")]` - Match strings under simple Unicode case folding,
+//!   which maps each character to a single character, instead of full case folding, so that
+//!   `"Straße"` matches `"STRAẞE"` but not `"STRASSE"`. Both fold a final `'ς'` to `'σ'`. Requires
+//!   the `unicase` feature.
+//! - `#[string_enum(catch_all_with = path::to::fn)]` - Give a function of type `fn(&str) ->
+//!   Option<Self>` the chance to resolve strings matching no label or alias, as by consulting a
+//!   runtime table or normalizing the input, before falling back to the `default` or `other`
+//!   variant or failing.
+//! - `#[string_enum(completions)]` - Generate `completions(prefix)`, which returns the labels
+//!   starting with the given prefix, for shell completion and interactive prompts. Aliases are
+//!   included with `#[string_enum(completions = "aliases")]`. Prefixes are matched under the same
+//!   case folding as labels with the `unicase` feature.
+//! - `#[string_enum(convert_from = other::Type)]` - Implement `TryFrom<other::Type>`, which parses
+//!   the label of the other enum, as returned by its `AsRef<str>` implementation, as a label or
+//!   alias of this one, for bridging duplicated enums across API versions or crates. The value is
+//!   given back as the error when no variant matches.
+//! - `#[string_enum(default = Variant)]` - Parse unknown strings as the given variant instead of
+//!   failing. The generated `FromStr` implementation uses [`core::convert::Infallible`] as its
+//!   error type.
+//! - `#[string_enum(deny_overlap)]` - Fail to compile if a label or alias is used by more than one
+//!   variant, comparing spellings the way input is matched, so under case folding with the
+//!   `unicase` feature. Without it, strings are parsed as the first variant using them.
+//! - `#[string_enum(error = "unit")]` - Use `()` as the `FromStr` error type instead of generating
+//!   an error type, for targets where binary size matters more than diagnostics.
+//! - `#[string_enum(generate_tests)]` - Generate a test module checking that each variant is parsed
+//!   from the string it is formatted as, and that each alias is parsed as its variant. The enum
+//!   must also derive [`macro@SerializeLabeledStringEnum`], which implements the formatting. Cannot
+//!   be used with enums with type or const parameters.
+//! - `#[string_enum(header_name = "x-priority")]` - Implement `headers::Header` for the header of
+//!   the given name, decoding its first value as the enum and encoding the label. Labels and
+//!   aliases must be string literals of visible ASCII, which header values are limited to. Requires
+//!   the `headers` feature, and cannot be used with an `other` or `pattern` variant.
+//! - `#[string_enum(list_delimiter = ",")]` - Generate `parse_list(s)`, which parses labels
+//!   separated by the delimiter into a `Vec`, failing with the `FromStr` error of the first label
+//!   that does not parse. Requires the `alloc` feature.
+//! - `#[string_enum(null = Variant)]` - Deserialize null (or unit) values as the given variant.
+//! - `#[string_enum(on_unknown = path::to::fn)]` - Call a function of type `fn(&str)` with each
+//!   string matching no label or alias, before falling back to the `default` or `other` variant or
+//!   failing, so that unexpected values can be counted or logged.
+//! - `#[string_enum(parse_detailed)]` - Generate `parse_detailed(s)`, which parses like `FromStr`
+//!   and also returns a generated `{Enum}MatchKind` telling whether the string was the label, an
+//!   alias and which one, or a spelling in another case, for linting non-canonical spellings.
+//!   Requires an `other` variant, if any, to own its string.
+//! - `#[string_enum(parse_prefix)]` - Generate `parse_prefix(s)`, which parses the longest label or
+//!   alias at the start of the input and returns the variant with the rest of the input, for
+//!   tokenizers built on the enum's labels. Labels are matched under the same case folding as
+//!   `FromStr` with the `unicase` feature.
+//! - `#[string_enum(parser)]` - Generate a `{Enum}Parser` builder, whose `case_insensitive` and
+//!   `aliases` methods choose how strings are matched, and whose `parse(s)` method fails or falls
+//!   back like `FromStr` when nothing matches, for parsing the same enum strictly in one place and
+//!   leniently in another. Cannot be used with generic enums or with a `pattern` variant.
+//! - `#[string_enum(percent_decode)]` - Also match the percent-encoded spellings of each label and
+//!   alias, as found in query strings and path segments. The spellings are computed at compile
+//!   time, so no decoding takes place at runtime. Requires the `percent_decode` feature.
+//! - `#[string_enum(prost = path::to::ProtoEnum)]` - Convert to and from the given prost-generated
+//!   enum by variant name, implementing `From<Self>` for it, `TryFrom<ProtoEnum>`, which gives the
+//!   value back when no variant has its name, such as for `Unspecified`, and the same conversions
+//!   with the `i32` that prost messages store, which rely on the `TryFrom<i32>` implementation
+//!   generated by prost. Every variant must be a unit variant.
+//! - `#[string_enum(public_visitor)]` - Give the generated `{Enum}Visitor` type the visibility of
+//!   the enum, so that hand-written `Deserialize` implementations can delegate to it.
+//! - `#[string_enum(remote = "other_crate::Type")]` - Generate `deserialize(deserializer)`, which
+//!   deserializes a value of the foreign enum mirrored by the enum, as with
+//!   [`macro@SerializeLabeledStringEnum`].
+//! - `#[string_enum(seed)]` - Generate a `{Enum}Seed` type implementing `DeserializeSeed`, whose
+//!   `case_insensitive` and `allow_aliases` fields choose how strings are matched for each call.
+//!   Cannot be used with generic enums.
+//! - `#[string_enum(self_describing)]` - Deserialize with `deserialize_any` instead of
+//!   `deserialize_str`, so that self-describing formats presenting the value as something other
+//!   than a string, such as some YAML or TOML wrappers and untagged enums, still hand strings to
+//!   the visitor. Non-string values then fail with an invalid type error.
+//! - `#[string_enum(serde_as = TypeLabel)]` - Implement `serde_with::DeserializeAs` for the given
+//!   type instead of implementing `Deserialize` for the enum, as with
+//!   [`macro@SerializeLabeledStringEnum`]. Requires the `serde_with` feature.
+//! - `#[string_enum(uniffi)]` - Register the enum as a `uniffi` custom type backed by `String`, so
+//!   that foreign bindings see its labels, and parse strings passed back with `FromStr`. The crate
+//!   must call `uniffi::setup_scaffolding!()`, which defines the converter trait implemented.
+//!   Requires the `uniffi` feature, and cannot be used with generic enums or with an `other` or
+//!   `pattern` variant.
+//! - `#[string_enum(with_module = type_as_string)]` - Generate a module of the given name for
+//!   `#[serde(with = "type_as_string")]` instead of implementing `Deserialize`, as with
+//!   [`macro@SerializeLabeledStringEnum`].
+//!
+//! # Integrations
+//! Conversions with other crates are generated for each enum that asks for them with a container
+//! attribute, so that only enums in crates depending on the other crate refer to it.
+//!
+//! - `#[string_enum(json)]` - Implement `From<Enum>` for `serde_json::Value`, which serializes the
+//!   enum as `Serialize` does, and `TryFrom<&Value>`, which deserializes it as `Deserialize` does,
+//!   and generate `labels_json()`, which returns the labels as a JSON array for API metadata
+//!   endpoints that advertise the allowed values. The `#[string(other)]` variant has no label to
+//!   list. Requires the `alloc` feature.
+//! - `#[string_enum(pyo3)]` - Implement `IntoPyObject`, which converts the enum into a Python
+//!   string of its label, and `FromPyObject`, which parses a Python string with `FromStr`. Requires
+//!   the `std` feature. Targets pyo3 0.28.
+//! - `#[string_enum(wasm_bindgen)]` - Implement conversions between the enum and `JsValue` strings
+//!   for `wasm-bindgen`. Unlike the `serde_json::Value` conversions, these go through `Display` and
+//!   `FromStr` rather than `Serialize`, so a `JsValue` always holds the label as written,
+//!   regardless of `serialize_case`, `qualified` or `serialize_as`. Requires the `alloc` feature.
+//!   Targets wasm-bindgen 0.2.100 and later.
+//! - `#[string_enum(bson)]` - Implement conversions between the enum and `bson::Bson` values, which
+//!   go through `Serialize` and `Deserialize`, for storing the enum as a field in MongoDB. With
+//!   `serialize_as = "number"`, numbers must fit in an `i64`. Requires the `std` feature. Targets
+//!   bson 2.
+//! - `#[string_enum(redis)]` - Implement `ToRedisArgs`, which writes the label as `Display` does,
+//!   and `FromRedisValue`, which parses replies with `FromStr`, so the enum can be used directly as
+//!   Redis values and hash fields. Requires the `std` feature. Targets redis 0.32.
+//! - `#[string_enum(defmt)]` - Implement `defmt::Format`, which writes the label, so logs from
+//!   embedded targets show `"Fire"` without depending on `core::fmt`. Targets defmt 1.
+//! - `#[string_enum(minicbor)]` - Implement `minicbor::Encode` and `minicbor::Decode`, writing the
+//!   label as a CBOR text string, so no_std projects built on minicbor rather than serde share the
+//!   same labels. Targets minicbor 0.19.
+//! - `#[string_enum(zbus)]` - Implement `zvariant::Type` with the string signature `s` and
+//!   conversions to and from `zvariant::Value` and `OwnedValue`, so zbus services can expose the
+//!   enum directly as a D-Bus string property. Messages are written through `Serialize`, but the
+//!   `Value` conversions go through `Display` and `FromStr`, so a `Value` holds the label as
+//!   written, regardless of `serialize_case` or `qualified`. Cannot be used with `serialize_as =
+//!   "number"`. Requires the `std` feature.
+//! - `#[string_enum(heapless)]` - Generate `MAX_LABEL_LEN` and `to_heapless::<N>()`, which copies
+//!   the label into a `heapless::String<N>` whose capacity is checked against the longest label at
+//!   compile time. Targets heapless 0.8.
+//! - `#[string_enum(dialoguer)]` - Generate `prompt_select(prompt)`, which lists the labels in the
+//!   terminal with `dialoguer` and returns the chosen variant, for interactive CLI tools. Variants
+//!   holding a string are not offered. Requires the `std` feature. Targets dialoguer 0.11.
+//! - `#[string_enum(rkyv)]` - Implement `rkyv` archiving, storing each variant as its label or,
+//!   with `#[string_enum(archive_as = "index")]`, its index. Cannot be used with an `other` or
+//!   `pattern` variant. Targets rkyv 0.8.
+//! - `#[string_enum(sea_orm)]` - Implement `ActiveEnum`, storing each variant as its label. Active
+//!   enums must be enumerable, so this cannot be used with an `other` or `pattern` variant.
+//!   Requires the `std` feature. Targets sea-orm 1.1.
+//! - `#[string_enum(poem_openapi)]` - Implement poem-openapi's `Type`, `ParseFromJSON`, and
+//!   `ToJSON`, registering a string schema that lists the labels. The schema lists every label, so
+//!   this cannot be used with an `other` or `pattern` variant. Deriving crates must also depend on
+//!   `serde_json`. Requires the `std` feature. Targets poem-openapi 5.
 
 #![no_std]
 
 extern crate alloc;
 extern crate proc_macro;
 
-use alloc::fmt::format;
+use alloc::{
+    fmt::format,
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "unicase")]
+use parse::simple_fold;
 use parse::{
+    screaming_snake_case,
+    ArchiveAs,
+    CaseFold,
+    Completions,
+    ContainerAttrs,
+    ErrorType,
     Input,
+    Label,
     LabeledStringInput,
+    SerializeAs,
+    Variant,
 };
 use proc_macro::TokenStream;
-use proc_macro2::Span;
+use proc_macro2::{
+    Literal,
+    Span,
+};
 use quote::{
+    format_ident,
     quote,
+    quote_spanned,
     ToTokens,
 };
 use syn::{
+    ext::IdentExt,
     parse_macro_input,
+    parse_quote,
+    spanned::Spanned,
+    Fields,
+    GenericParam,
+    Generics,
     Ident,
+    Lifetime,
+    LifetimeParam,
+    LitStr,
+    Type,
+    Visibility,
 };
 
+mod bytes;
 mod parse;
+mod percent;
 
 /// Procedural macro for serializing enums as strings.
 ///
-/// Enums deriving this macro must have implemented [`core::fmt::Display`].
-#[proc_macro_derive(SerializeStringEnum)]
+/// Enums deriving this macro must have implemented [`core::fmt::Display`]. Labels given with
+/// `#[string = ...]` or `#[alias = ...]` are ignored, so they raise a deprecation warning pointing
+/// to [`SerializeLabeledStringEnum`], unless the enum allows `deprecated` because another derive
+/// uses them.
+#[proc_macro_derive(SerializeStringEnum, attributes(string, alias))]
 pub fn derive_serialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as Input);
+    let ignored_labels =
+        warn_ignored_labels(&input, "SerializeStringEnum", "SerializeLabeledStringEnum");
     let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     TokenStream::from(quote! {
-        impl serde::Serialize for #ident {
-            fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> where S: serde::Serializer {
+        impl #impl_generics ::serde::Serialize for #ident #ty_generics #where_clause {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
                 serializer.collect_str(self)
             }
         }
+
+        #ignored_labels
     })
 }
 
 /// Procedural macro for deserializing strings to enum variants.
 ///
-/// Enums deriving this macro must have implemented [`core::str::FromStr`].
-#[proc_macro_derive(DeserializeStringEnum)]
+/// Enums deriving this macro must have implemented [`core::str::FromStr`]. Labels given with
+/// `#[string = ...]` or `#[alias = ...]` are ignored, so they raise a deprecation warning pointing
+/// to [`DeserializeLabeledStringEnum`], unless the enum allows `deprecated` because another derive
+/// uses them.
+#[proc_macro_derive(DeserializeStringEnum, attributes(string, alias))]
 pub fn derive_deserialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as Input);
+    let ignored_labels = warn_ignored_labels(
+        &input,
+        "DeserializeStringEnum",
+        "DeserializeLabeledStringEnum",
+    );
     let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let de_generics = de_generics(&input.generics);
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
 
     let visitor_ident = Ident::new(&format(format_args!("{ident}Visitor")), Span::call_site());
-
     TokenStream::from(quote! {
-        struct #visitor_ident;
+        struct #visitor_ident #impl_generics #where_clause {
+            marker: ::core::marker::PhantomData<#ident #ty_generics>,
+        }
 
-        impl<'de> serde::de::Visitor<'de> for #visitor_ident {
-            type Value = #ident;
+        impl #de_impl_generics ::serde::de::Visitor<'de> for #visitor_ident #ty_generics #where_clause {
+            type Value = #ident #ty_generics;
 
-            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
-                formatter.write_fmt(format_args!("a valid {} string value", stringify!(#ident)))
+            fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                formatter.write_fmt(::core::format_args!("a valid {} string value", ::core::stringify!(#ident)))
             }
 
-           fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E> where E: serde::de::Error {
-            match Self::Value::from_str(&v) {
-                Ok(v) => Ok(v),
-                Err(_) => Err(E::invalid_value(serde::de::Unexpected::Str(&v), &self)),
+           fn visit_str<E>(self, v: &str) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+            match <Self::Value as ::core::str::FromStr>::from_str(&v) {
+                ::core::result::Result::Ok(v) => ::core::result::Result::Ok(v),
+                ::core::result::Result::Err(_) => ::core::result::Result::Err(E::invalid_value(::serde::de::Unexpected::Str(&v), &self)),
             }
            }
         }
 
-        impl<'de> serde::Deserialize<'de> for #ident {
-            fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error> where D: serde::Deserializer<'de> {
-                deserializer.deserialize_str(#visitor_ident)
+        impl #de_impl_generics ::serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: ::serde::Deserializer<'de> {
+                deserializer.deserialize_str(#visitor_ident { marker: ::core::marker::PhantomData })
             }
         }
+
+        #ignored_labels
     })
 }
 
 /// Procedural macro for serializing enums as strings, where each variant is labeled with a
 /// `#[string = ...]` attribute.
-#[proc_macro_derive(SerializeLabeledStringEnum, attributes(string))]
+///
+/// Also implements [`core::fmt::Display`] and [`AsRef<str>`], which give the label. See the
+/// [crate documentation](crate#serializing) for the other generated items and the container
+/// attributes.
+#[proc_macro_derive(
+    SerializeLabeledStringEnum,
+    attributes(string, alias, number, string_enum)
+)]
 pub fn derive_labeled_serialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as LabeledStringInput);
+    if input.byte_labels {
+        return bytes::derive_serialize(input);
+    }
+    let description = description(&input);
+    let label_bytes = label_bytes(&input);
+    let label_table = label_table(&input);
+    let label_constants = label_constants(&input);
+    let subset_of = subset_of(&input);
+    let variant_ids = variant_ids(&input);
     let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let match_variants = input.variants.iter().map(|variant| {
-        let string = variant.attrs.string.as_ref().unwrap();
-        let variant = &variant.ident;
+    // The pattern matching each variant and the string it is formatted as.
+    let label_arms = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = &variant.ident;
+            if variant.attrs.holds_string() {
+                return (quote! { Self::#variant_ident(value) }, quote! { value });
+            }
+            let pattern = variant.pattern();
+            let string = variant.attrs.string.as_ref().unwrap();
+            (quote! { Self::#pattern }, quote! { #string })
+        })
+        .collect::<Vec<_>>();
+    let display_arms = label_arms
+        .iter()
+        .zip(&input.variants)
+        .map(|((pattern, label), variant)| match &variant.attrs.alt {
+            Some(alt) => (
+                pattern,
+                quote! { f.pad(if f.alternate() { #alt } else { #label }) },
+            ),
+            None => (pattern, quote! { f.pad(#label) }),
+        })
+        .collect::<Vec<_>>();
+    let (match_variants, display_chunks) = if display_arms.len() > CHUNK_SIZE {
+        // Dispatch to helpers that each format a chunk of the variants.
+        let mut match_variants = Vec::new();
+        let mut display_chunks = Vec::new();
+        for (i, chunk) in display_arms.chunks(CHUNK_SIZE).enumerate() {
+            let chunk_ident = format_ident!("__fmt_label_{}", i);
+            let patterns = chunk.iter().map(|(pattern, _)| pattern);
+            let arms = chunk
+                .iter()
+                .map(|(pattern, body)| quote! { #pattern => #body, });
+            match_variants.push(quote! {
+                #(#patterns)|* => Self::#chunk_ident(self, f),
+            });
+            display_chunks.push(quote! {
+                fn #chunk_ident(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(#arms)*
+                        _ => ::core::unreachable!(),
+                    }
+                }
+            });
+        }
+        let display_chunks = quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #(#display_chunks)*
+            }
+        };
+        (match_variants, display_chunks)
+    } else {
+        let match_variants = display_arms
+            .iter()
+            .map(|(pattern, body)| quote! { #pattern => #body, })
+            .collect();
+        (match_variants, quote! {})
+    };
+
+    let as_ref_arms = label_arms
+        .iter()
+        .map(|(pattern, label)| quote! { #pattern => #label, });
+
+    let borrow_str = if input.attrs.borrow_str {
         quote! {
-            Self::#variant => write!(f, #string),
+            impl #impl_generics ::core::borrow::Borrow<str> for #ident #ty_generics #where_clause {
+                fn borrow(&self) -> &str {
+                    ::core::convert::AsRef::<str>::as_ref(self)
+                }
+            }
         }
-    });
+    } else {
+        quote! {}
+    };
 
-    TokenStream::from(quote! {
-        impl core::fmt::Display for #ident {
-            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                match self {
-                    #(#match_variants)*
+    let hash_by_label = if input.attrs.hash_by_label {
+        quote! {
+            impl #impl_generics ::core::hash::Hash for #ident #ty_generics #where_clause {
+                fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                    ::core::hash::Hash::hash(::core::convert::AsRef::<str>::as_ref(self), state)
                 }
             }
         }
+    } else {
+        quote! {}
+    };
 
-        impl serde::Serialize for #ident {
-            fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> where S: serde::Serializer {
-                serializer.collect_str(self)
+    let remote = input.attrs.remote.as_ref().map(|remote| {
+        let variant_idents = input
+            .variants
+            .iter()
+            .map(|variant| &variant.ident)
+            .collect::<Vec<_>>();
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Serializes a value of the remote enum as the label of the variant of the same
+                /// name, for use with `#[serde(with = ...)]`.
+                pub fn serialize<S>(value: &#remote, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                    let value = match value {
+                        #(#remote::#variant_idents => Self::#variant_idents,)*
+                    };
+                    ::serde::Serialize::serialize(&value, serializer)
+                }
             }
         }
-    })
-}
+    });
 
-fn wrap_unicase<T>(t: &T) -> proc_macro2::TokenStream
-where
-    T: ToTokens,
-{
-    if cfg!(feature = "unicase") {
+    let format_list = input.attrs.list_delimiter.as_ref().map(|delimiter| {
+        let string_type = string_type();
         quote! {
-            unicase::UniCase::new(#t)
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Formats the labels of the values, separated by the list delimiter.
+                pub fn format_list(values: &[Self]) -> #string_type {
+                    let mut list = #string_type::new();
+                    for (i, value) in ::core::iter::Iterator::enumerate(values.iter()) {
+                        if i > 0 {
+                            list.push_str(#delimiter);
+                        }
+                        list.push_str(::core::convert::AsRef::<str>::as_ref(value));
+                    }
+                    list
+                }
+            }
+        }
+    });
+
+    let cow = if cfg!(any(feature = "std", feature = "alloc")) {
+        let (cow_type, string_type) = (cow_type(), string_type());
+        let cow_arms = input.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            if variant.attrs.holds_string() {
+                return quote! {
+                    #ident::#variant_ident(value) => #cow_type::Owned(::core::convert::Into::<#string_type>::into(value)),
+                };
+            }
+            let pattern = variant.pattern();
+            let string = variant.attrs.string.as_ref().unwrap();
+            quote! { #ident::#pattern => #cow_type::Borrowed(#string), }
+        });
+        quote! {
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for #cow_type<'static, str> #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    match value {
+                        #(#cow_arms)*
+                    }
+                }
+            }
         }
     } else {
+        quote! {}
+    };
+
+    // Conversions into serialized values go through the same code as `Serialize`, which is a
+    // hidden method when the enum does not implement `Serialize` itself.
+    let serialize_into = |serializer: proc_macro2::TokenStream| {
+        if input.attrs.serde_as.is_some() || input.attrs.with_module.is_some() {
+            quote! { value.__serialize(#serializer) }
+        } else {
+            quote! { ::serde::Serialize::serialize(&value, #serializer) }
+        }
+    };
+
+    let to_string = to_string_trait();
+    let json = if input.attrs.json {
+        let serialize = serialize_into(quote! { ::serde_json::value::Serializer });
         quote! {
-            #t
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::serde_json::Value #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    // Labels are serialized as strings or numbers, which always fit in a `Value`.
+                    match #serialize {
+                        ::core::result::Result::Ok(value) => value,
+                        ::core::result::Result::Err(_) => ::core::unreachable!(),
+                    }
+                }
+            }
+
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Returns the labels of the enum as a JSON array, in declaration order, for
+                /// advertising the allowed values in API metadata.
+                pub fn labels_json() -> ::serde_json::Value {
+                    ::serde_json::Value::Array(::core::iter::Iterator::collect(::core::iter::Iterator::map(
+                        ::core::iter::IntoIterator::into_iter(Self::__LABELS),
+                        |label| ::serde_json::Value::String(::core::convert::From::from(*label)),
+                    )))
+                }
+            }
         }
-    }
-}
+    } else {
+        quote! {}
+    };
 
-/// Procedural macro for deserializing strings to enum variants, where each variant is labeled with
-/// a `#[string = ...]` attribute.
-#[proc_macro_derive(DeserializeLabeledStringEnum, attributes(string, alias))]
-pub fn derive_labeled_deserialize(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as LabeledStringInput);
-    let call_site = Span::call_site();
-    let ident = input.ident;
-    let visitor_ident = Ident::new(&format(format_args!("{ident}Visitor")), call_site);
-    let input_ident = Ident::new("s", call_site);
+    let pyo3 = if input.attrs.pyo3 {
+        let mut py_generics = input.generics.clone();
+        py_generics.params.insert(0, parse_quote! { 'py });
+        let (py_impl_generics, _, _) = py_generics.split_for_impl();
+        quote! {
+            impl #py_impl_generics ::pyo3::IntoPyObject<'py> for #ident #ty_generics #where_clause {
+                type Target = ::pyo3::types::PyString;
+                type Output = ::pyo3::Bound<'py, ::pyo3::types::PyString>;
+                type Error = ::core::convert::Infallible;
 
-    let match_variants = input.variants.iter().map(|variant| {
-        let variant_ident = &variant.ident;
-        let alias_match = variant.attrs.aliases.iter().map(|alias| {
-            let alias = wrap_unicase(alias);
-            quote! {
-                if s == #alias {
-                    return Ok(Self::#variant_ident)
+                fn into_pyobject(self, py: ::pyo3::Python<'py>) -> ::core::result::Result<::pyo3::Bound<'py, ::pyo3::types::PyString>, ::core::convert::Infallible> {
+                    ::core::result::Result::Ok(::pyo3::types::PyString::new(py, &::std::string::ToString::to_string(&self)))
                 }
             }
-        });
-        let string = variant.attrs.string.as_ref().unwrap();
-        let string = wrap_unicase(string);
+        }
+    } else {
+        quote! {}
+    };
+
+    let wasm_bindgen = if input.attrs.wasm_bindgen {
         quote! {
-            if #input_ident == #string {
-                return Ok(Self::#variant_ident)
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::wasm_bindgen::JsValue #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    ::wasm_bindgen::JsValue::from_str(&#to_string::to_string(&value))
+                }
             }
-            #(#alias_match)*
         }
-    });
+    } else {
+        quote! {}
+    };
+
+    let bson = if input.attrs.bson {
+        let serialize = serialize_into(quote! { ::bson::Serializer::new() });
+        quote! {
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::bson::Bson #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    // Numbers are checked to fit in an `i64` when the enum is parsed.
+                    match #serialize {
+                        ::core::result::Result::Ok(value) => value,
+                        ::core::result::Result::Err(_) => ::core::unreachable!(),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
 
-    let error_type = if cfg!(feature = "std") {
+    let redis = if input.attrs.redis {
         quote! {
-            std::string::String
+            impl #impl_generics ::redis::ToRedisArgs for #ident #ty_generics #where_clause {
+                fn write_redis_args<W>(&self, out: &mut W) where W: ?::core::marker::Sized + ::redis::RedisWrite {
+                    ::redis::RedisWrite::write_arg_fmt(out, self)
+                }
+            }
         }
-    } else if cfg!(feature = "alloc") {
+    } else {
+        quote! {}
+    };
+
+    let defmt = if input.attrs.defmt {
+        let match_labels = label_arms.iter().map(|(pattern, label)| {
+            quote! { #pattern => ::defmt::Format::format(::core::convert::AsRef::<str>::as_ref(#label), f), }
+        });
         quote! {
-            alloc::string::String
+            impl #impl_generics ::defmt::Format for #ident #ty_generics #where_clause {
+                fn format(&self, f: ::defmt::Formatter) {
+                    match self {
+                        #(#match_labels)*
+                    }
+                }
+            }
         }
     } else {
+        quote! {}
+    };
+
+    let minicbor = if input.attrs.minicbor {
+        let mut encode_generics = input.generics.clone();
+        encode_generics.params.push(parse_quote! { __C });
+        let (encode_impl_generics, _, _) = encode_generics.split_for_impl();
         quote! {
-            &'static str
+            impl #encode_impl_generics ::minicbor::Encode<__C> for #ident #ty_generics #where_clause {
+                fn encode<W>(&self, e: &mut ::minicbor::Encoder<W>, _: &mut __C) -> ::core::result::Result<(), ::minicbor::encode::Error<W::Error>>
+                where
+                    W: ::minicbor::encode::Write,
+                {
+                    e.str(::core::convert::AsRef::<str>::as_ref(self))?;
+                    ::core::result::Result::Ok(())
+                }
+            }
         }
+    } else {
+        quote! {}
     };
 
-    let error = if cfg!(feature = "std") {
+    let zbus = if input.attrs.zbus {
         quote! {
-            std::format!("invalid {}: {}", stringify!(#ident), #input_ident)
+            impl #impl_generics ::zvariant::Type for #ident #ty_generics #where_clause {
+                const SIGNATURE: &'static ::zvariant::Signature = &::zvariant::Signature::Str;
+            }
+
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::zvariant::Value<'_> #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    ::core::convert::From::from(#to_string::to_string(&value))
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::zvariant::OwnedValue #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    ::core::convert::From::from(<::zvariant::Str<'_> as ::core::convert::From<_>>::from(#to_string::to_string(&value)))
+                }
+            }
         }
-    } else if cfg!(feature = "alloc") {
+    } else {
+        quote! {}
+    };
+
+    let heapless = if input.attrs.heapless {
+        let labels = input
+            .variants
+            .iter()
+            .filter_map(|variant| variant.attrs.string.as_ref())
+            .collect::<Vec<_>>();
+        let label_count = labels.len();
+        let match_labels = label_arms
+            .iter()
+            .map(|(pattern, label)| quote! { #pattern => #label, });
         quote! {
-            alloc::fmt::format(format_args!("invalid {}: {}", stringify!(#ident), #input_ident))
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// The length in bytes of the longest label.
+                pub const MAX_LABEL_LEN: usize = {
+                    let lens: [usize; #label_count] = [#(#labels.len()),*];
+                    let mut max = 0;
+                    let mut i = 0;
+                    while i < lens.len() {
+                        if lens[i] > max {
+                            max = lens[i];
+                        }
+                        i += 1;
+                    }
+                    max
+                };
+
+                /// Returns the label as a `heapless::String`, whose capacity must fit every label.
+                ///
+                /// Fails only if the variant holds a string longer than the capacity.
+                pub fn to_heapless<const __N: usize>(&self) -> ::core::result::Result<::heapless::String<__N>, ()> {
+                    const {
+                        ::core::assert!(__N >= Self::MAX_LABEL_LEN, "capacity is smaller than the longest label");
+                    }
+                    let label: &str = match self {
+                        #(#match_labels)*
+                    };
+                    let mut string = ::heapless::String::new();
+                    string.push_str(label).map_err(|_| ())?;
+                    ::core::result::Result::Ok(string)
+                }
+            }
         }
     } else {
+        quote! {}
+    };
+
+    // Variants holding a string cannot be chosen from a list, so only labeled variants are offered.
+    let dialoguer = if input.attrs.dialoguer {
+        let labeled_variants = input
+            .variants
+            .iter()
+            .filter(|variant| !variant.attrs.holds_string())
+            .collect::<Vec<_>>();
+        let labels = labeled_variants
+            .iter()
+            .map(|variant| variant.attrs.string.as_ref().unwrap());
+        let constructors = labeled_variants.iter().map(|variant| variant.constructor());
+        let indices = 0..labeled_variants.len();
         quote! {
-            "invalid value"
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Asks the user to choose a variant from a list of the labels in the terminal.
+                pub fn prompt_select(prompt: &str) -> ::dialoguer::Result<Self> {
+                    let index = ::dialoguer::Select::new()
+                        .with_prompt(prompt)
+                        .items(&[#(#labels),*])
+                        .default(0)
+                        .interact()?;
+                    ::core::result::Result::Ok(match index {
+                        #(#indices => Self::#constructors,)*
+                        _ => ::core::unreachable!(),
+                    })
+                }
+            }
         }
+    } else {
+        quote! {}
     };
-    let unicase_input = wrap_unicase(&input_ident);
 
-    TokenStream::from(quote! {
-        impl core::str::FromStr for #ident {
-            type Err = #error_type;
-            fn from_str(#input_ident: &str) -> core::result::Result<Self, Self::Err> {
-                let #input_ident = #unicase_input;
-                #(#match_variants)*
-                Err(#error)
+    let rkyv = if input.attrs.rkyv {
+        let mut ser_generics = input.generics.clone();
+        ser_generics.params.push(parse_quote! {
+            __S: ::rkyv::rancor::Fallible + ::rkyv::ser::Writer + ?::core::marker::Sized
+        });
+        ser_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { __S::Error: ::rkyv::rancor::Source });
+        let (ser_impl_generics, _, ser_where_clause) = ser_generics.split_for_impl();
+        let patterns = input
+            .variants
+            .iter()
+            .map(|variant| variant.pattern())
+            .collect::<Vec<_>>();
+        match input.attrs.archive_as {
+            ArchiveAs::Label => {
+                let labels = input
+                    .variants
+                    .iter()
+                    .map(|variant| variant.attrs.string.as_ref().unwrap());
+                quote! {
+                    impl #impl_generics #ident #ty_generics #where_clause {
+                        fn __archived_label(&self) -> &'static str {
+                            match self {
+                                #(Self::#patterns => #labels,)*
+                            }
+                        }
+                    }
+
+                    impl #impl_generics ::rkyv::Archive for #ident #ty_generics #where_clause {
+                        type Archived = ::rkyv::string::ArchivedString;
+                        type Resolver = ::rkyv::string::StringResolver;
+                        fn resolve(&self, resolver: Self::Resolver, out: ::rkyv::Place<Self::Archived>) {
+                            ::rkyv::string::ArchivedString::resolve_from_str(self.__archived_label(), resolver, out)
+                        }
+                    }
+
+                    impl #ser_impl_generics ::rkyv::Serialize<__S> for #ident #ty_generics #ser_where_clause {
+                        fn serialize(&self, serializer: &mut __S) -> ::core::result::Result<Self::Resolver, __S::Error> {
+                            ::rkyv::string::ArchivedString::serialize_from_str(self.__archived_label(), serializer)
+                        }
+                    }
+                }
+            }
+            ArchiveAs::Index => {
+                let indices = (0..patterns.len() as u32).map(Literal::u32_unsuffixed);
+                quote! {
+                    impl #impl_generics ::rkyv::Archive for #ident #ty_generics #where_clause {
+                        type Archived = ::rkyv::primitive::ArchivedU32;
+                        type Resolver = ();
+                        fn resolve(&self, resolver: Self::Resolver, out: ::rkyv::Place<Self::Archived>) {
+                            let index: u32 = match self {
+                                #(Self::#patterns => #indices,)*
+                            };
+                            ::rkyv::Archive::resolve(&index, resolver, out)
+                        }
+                    }
+
+                    impl #ser_impl_generics ::rkyv::Serialize<__S> for #ident #ty_generics #ser_where_clause {
+                        fn serialize(&self, _: &mut __S) -> ::core::result::Result<Self::Resolver, __S::Error> {
+                            ::core::result::Result::Ok(())
+                        }
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let qualifier = input.attrs.qualifier(&ident);
+    let serialize = match (
+        input.attrs.serialize_as,
+        input.attrs.serialize_case,
+        &qualifier,
+    ) {
+        (SerializeAs::String, None, None) if input.char_labels => {
+            let match_labels = input.variants.iter().map(|variant| {
+                let pattern = variant.pattern();
+                let label = variant.attrs.string.as_ref().unwrap().as_lit().unwrap();
+                let char = Literal::character(label.value().chars().next().unwrap());
+                quote! {
+                    Self::#pattern => serializer.serialize_char(#char),
+                }
+            });
+            quote! {
+                match self {
+                    #(#match_labels)*
+                }
+            }
+        }
+        (SerializeAs::String, None, None) => quote! {
+            serializer.collect_str(self)
+        },
+        (SerializeAs::String, case, qualifier) => {
+            let match_labels = input.variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                if variant.attrs.holds_string() {
+                    return quote! {
+                        Self::#variant_ident(value) => serializer.serialize_str(value),
+                    };
+                }
+                let serialize_label = match variant.attrs.string.as_ref().unwrap() {
+                    Label::Lit(string) => {
+                        let string = match case {
+                            Some(case) => case.apply(string),
+                            None => string.clone(),
+                        };
+                        let string = match qualifier {
+                            Some(qualifier) => LitStr::new(
+                                &format(format_args!("{qualifier}{}", string.value())),
+                                string.span(),
+                            ),
+                            None => string,
+                        };
+                        quote! { serializer.serialize_str(#string) }
+                    }
+                    Label::Expr(string) => quote! {
+                        serializer.collect_str(&::core::format_args!("{}{}", #qualifier, #string))
+                    },
+                    Label::Bytes(_) => unreachable!("byte string labels are serialized as bytes"),
+                };
+                let pattern = variant.pattern();
+                quote! {
+                    Self::#pattern => #serialize_label,
+                }
+            });
+            quote! {
+                match self {
+                    #(#match_labels)*
+                }
+            }
+        }
+        (SerializeAs::Number, ..) => {
+            let match_numbers = input.variants.iter().map(|variant| {
+                let pattern = variant.pattern();
+                let number = Literal::u64_unsuffixed(variant.attrs.number.unwrap());
+                quote! {
+                    Self::#pattern => serializer.serialize_u64(#number),
+                }
+            });
+            quote! {
+                match self {
+                    #(#match_numbers)*
+                }
             }
         }
+    };
+
+    let inline = inline_hint(&input.attrs);
+
+    // Labels are serialized through a helper, which serializing an alias falls back to.
+    let (serialize, alias_switch) = if input.attrs.alias_switch || !input.attrs.profiles.is_empty()
+    {
+        let qualifier = &qualifier;
+        let match_aliases = input.variants.iter().flat_map(|variant| {
+            let pattern = variant.pattern();
+            variant
+                .attrs
+                .aliases
+                .iter()
+                .enumerate()
+                .map(move |(alias_index, alias)| {
+                    // Aliases are written like labels, with the same case and qualifier.
+                    let serialize_alias = match alias {
+                        Label::Lit(alias) => {
+                            let alias = match input.attrs.serialize_case {
+                                Some(case) => case.apply(alias),
+                                None => alias.clone(),
+                            };
+                            let alias = match qualifier {
+                                Some(qualifier) => LitStr::new(
+                                    &format(format_args!("{qualifier}{}", alias.value())),
+                                    alias.span(),
+                                ),
+                                None => alias,
+                            };
+                            quote! { serializer.serialize_str(#alias) }
+                        }
+                        alias => quote! {
+                            serializer.collect_str(&::core::format_args!("{}{}", #qualifier, #alias))
+                        },
+                    };
+                    quote! { (Self::#pattern, #alias_index) => #serialize_alias, }
+                })
+        });
+        let alias_switch = quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Serializes the alias at the given position among the aliases of the variant, or
+                /// serializes the variant as usual if it has fewer aliases, to keep emitting a
+                /// legacy spelling during a staged rollout.
+                pub fn serialize_as_alias<S>(&self, alias_index: usize, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                    match (self, alias_index) {
+                        #(#match_aliases)*
+                        _ => self.__serialize_label(serializer),
+                    }
+                }
+
+                fn __serialize_label<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                    #serialize
+                }
+            }
+        };
+        let (profile, serialize) = if input.attrs.profiles.is_empty() {
+            (quote! {}, serialize)
+        } else {
+            let match_profiles = input
+                .attrs
+                .profiles
+                .iter()
+                .map(|(name, index)| quote! { #name => ::core::option::Option::Some(#index), });
+            let profile = quote! {
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /// Serializes the variant as the given profile does, or as usual if the
+                    /// profile is not declared.
+                    pub fn serialize_with_profile<S>(&self, profile: &str, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                        match Self::__profile_alias(profile) {
+                            ::core::option::Option::Some(alias_index) => self.serialize_as_alias(alias_index, serializer),
+                            ::core::option::Option::None => self.__serialize_label(serializer),
+                        }
+                    }
 
-        struct #visitor_ident;
+                    /// Calls `f` with every serialization of the enum on the current thread using
+                    /// the given profile, restoring the previous profile when `f` returns or panics.
+                    /// An undeclared profile serializes labels.
+                    pub fn with_profile<R>(profile: &str, f: impl ::core::ops::FnOnce() -> R) -> R {
+                        struct Restore(
+                            &'static ::std::thread::LocalKey<::core::cell::Cell<::core::option::Option<usize>>>,
+                            ::core::option::Option<usize>,
+                        );
+                        impl ::core::ops::Drop for Restore {
+                            fn drop(&mut self) {
+                                self.0.with(|profile| profile.set(self.1));
+                            }
+                        }
+                        let key = Self::__profile();
+                        let _restore = Restore(key, key.with(|current| current.replace(Self::__profile_alias(profile))));
+                        f()
+                    }
 
-        impl<'de> serde::de::Visitor<'de> for #visitor_ident {
-            type Value = #ident;
+                    fn __profile_alias(profile: &str) -> ::core::option::Option<usize> {
+                        match profile {
+                            #(#match_profiles)*
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+
+                    fn __profile() -> &'static ::std::thread::LocalKey<::core::cell::Cell<::core::option::Option<usize>>> {
+                        ::std::thread_local! {
+                            static PROFILE: ::core::cell::Cell<::core::option::Option<usize>> = ::core::cell::Cell::new(::core::option::Option::None);
+                        }
+                        &PROFILE
+                    }
+                }
+            };
+            let serialize = quote! {
+                match Self::__profile().with(::core::cell::Cell::get) {
+                    ::core::option::Option::Some(alias_index) => self.serialize_as_alias(alias_index, serializer),
+                    ::core::option::Option::None => self.__serialize_label(serializer),
+                }
+            };
+            (profile, serialize)
+        };
+        (serialize, quote! { #alias_switch #profile })
+    } else {
+        (serialize, quote! {})
+    };
 
-            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
-                formatter.write_fmt(format_args!("a valid {} string value", stringify!(#ident)))
+    let serialize_impl = if input.attrs.serde_as.is_some() || input.attrs.with_module.is_some() {
+        // The module generated by `with_module` is emitted by the `Deserialize` derive.
+        let serialize_as = input.attrs.serde_as.as_ref().map(|adapter| {
+            quote! {
+                impl #impl_generics ::serde_with::SerializeAs<#ident #ty_generics> for #adapter #where_clause {
+                    fn serialize_as<S>(source: &#ident #ty_generics, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                        source.__serialize(serializer)
+                    }
+                }
+            }
+        });
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #inline
+                fn __serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                    #serialize
+                }
             }
 
-           fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E> where E: serde::de::Error {
-            use core::str::FromStr;
-            match Self::Value::from_str(&v) {
-                Ok(v) => Ok(v),
-                Err(_) => Err(E::invalid_value(serde::de::Unexpected::Str(&v), &self)),
+            #serialize_as
+        }
+    } else {
+        quote! {
+            impl #impl_generics ::serde::Serialize for #ident #ty_generics #where_clause {
+                #inline
+                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                    #serialize
+                }
             }
-           }
         }
+    };
+
+    debug_expand(
+        &input.attrs,
+        "SerializeLabeledStringEnum",
+        quote! {
+            impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                #inline
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(#match_variants)*
+                    }
+                }
+            }
+
+            #display_chunks
 
-        impl<'de> serde::Deserialize<'de> for #ident {
-            fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error> where D: serde::Deserializer<'de> {
-                deserializer.deserialize_str(#visitor_ident)
+            impl #impl_generics ::core::convert::AsRef<str> for #ident #ty_generics #where_clause {
+                fn as_ref(&self) -> &str {
+                    match self {
+                        #(#as_ref_arms)*
+                    }
+                }
             }
+
+            #borrow_str
+
+            #hash_by_label
+
+            #alias_switch
+
+            #remote
+
+            #format_list
+
+            #cow
+
+            #description
+
+            #label_bytes
+
+            #label_table
+
+            #label_constants
+
+            #subset_of
+
+            #variant_ids
+
+            #serialize_impl
+
+            #json
+
+            #pyo3
+
+            #wasm_bindgen
+
+            #bson
+
+            #redis
+
+            #rkyv
+
+            #defmt
+
+            #minicbor
+
+            #zbus
+
+            #heapless
+
+            #dialoguer
+        },
+    )
+}
+
+/// Procedural macro for formatting enums with [`core::fmt::Debug`] using the label of each variant,
+/// where each variant is labeled with a `#[string = ...]` attribute.
+///
+/// Labels are formatted like string slices, so log output matches what appears on the wire.
+#[proc_macro_derive(DebugLabeledStringEnum, attributes(string, string_enum))]
+pub fn derive_labeled_debug(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as LabeledStringInput);
+    let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let match_variants = input.variants.iter().map(|variant| {
+        let variant_ident = &variant.ident;
+        if variant.attrs.holds_string() {
+            return quote! {
+                Self::#variant_ident(value) => ::core::fmt::Debug::fmt(value, f),
+            };
         }
-    })
+        let pattern = variant.pattern();
+        let string = variant.attrs.string.as_ref().unwrap();
+        quote! {
+            Self::#pattern => ::core::fmt::Debug::fmt(#string, f),
+        }
+    });
+
+    debug_expand(
+        &input.attrs,
+        "DebugLabeledStringEnum",
+        quote! {
+            impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(#match_variants)*
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Returns the generated code, followed by a warning that displays it when
+/// `#[string_enum(debug_expand)]` is set.
+///
+/// Procedural macros cannot emit notes on stable Rust, so the warning is raised by using a
+/// deprecated item whose deprecation note holds the generated code.
+fn debug_expand(
+    attrs: &ContainerAttrs,
+    derive: &str,
+    expanded: proc_macro2::TokenStream,
+) -> TokenStream {
+    let span = match attrs.debug_expand {
+        Some(span) => span,
+        None => return TokenStream::from(expanded),
+    };
+    // The lint is only reported for spans in the user's code, so the item is used at the span of
+    // the attribute.
+    let debug_expand = Ident::new("DebugExpand", span);
+    let note = format(format_args!("{derive} expands to:\n{expanded}"));
+    TokenStream::from(quote! {
+        #expanded
+
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct DebugExpand;
+            let _ = #debug_expand;
+        };
+    })
+}
+
+/// Warns that labels given to a derive using the enum's own implementation are ignored, unless
+/// the enum allows `deprecated`, as it may when a labeled derive on the same enum uses them.
+fn warn_ignored_labels(
+    input: &Input,
+    derive: &str,
+    labeled_derive: &str,
+) -> proc_macro2::TokenStream {
+    let label = match input.first_label() {
+        Some(label) => label,
+        None => return quote! {},
+    };
+    // As with `debug_expand`, the warning is a deprecation reported at the span of the label.
+    let ignored_labels = Ident::new("IgnoredLabels", label.span());
+    let note = format(format_args!(
+        "labels are ignored by {derive}, which uses the enum's own implementation; derive \
+         {labeled_derive} instead"
+    ));
+    let allow = &input.allow;
+    quote! {
+        #(#allow)*
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct IgnoredLabels;
+            let _ = #ignored_labels;
+        };
+    }
+}
+
+/// Returns the generics for implementing a trait parameterized by the `'de` lifetime, which must
+/// outlive every lifetime of the type.
+fn de_generics(generics: &Generics) -> Generics {
+    let mut de_lifetime = LifetimeParam::new(Lifetime::new("'de", Span::call_site()));
+    de_lifetime
+        .bounds
+        .extend(generics.lifetimes().map(|param| param.lifetime.clone()));
+    let mut de_generics = generics.clone();
+    de_generics
+        .params
+        .insert(0, GenericParam::Lifetime(de_lifetime));
+    de_generics
+}
+
+/// Wraps a string in `unicase::UniCase` when it is compared under full case folding with the
+/// `unicase` feature.
+fn wrap_unicase<T>(t: &T, case_fold: CaseFold) -> proc_macro2::TokenStream
+where
+    T: ToTokens,
+{
+    if cfg!(feature = "unicase") && case_fold == CaseFold::Full {
+        quote! {
+            ::unicase::UniCase::new(#t)
+        }
+    } else {
+        quote! {
+            #t
+        }
+    }
+}
+
+/// Generates a check of `value`, the input as wrapped by [`wrap_unicase`], against a spelling.
+fn matches_spelling(spelling: &Label, case_fold: CaseFold) -> proc_macro2::TokenStream {
+    if cfg!(feature = "unicase") && case_fold == CaseFold::Simple {
+        eq_folded(&quote! { value }, spelling, case_fold)
+    } else {
+        let spelling = wrap_unicase(spelling, case_fold);
+        quote! { value == #spelling }
+    }
+}
+
+/// Generates a comparison of two strings under the given case folding, for the `unicase` feature.
+fn eq_folded<A, B>(a: &A, b: &B, case_fold: CaseFold) -> proc_macro2::TokenStream
+where
+    A: ToTokens,
+    B: ToTokens,
+{
+    match case_fold {
+        CaseFold::Full => quote! { ::unicase::UniCase::new(#a) == ::unicase::UniCase::new(#b) },
+        CaseFold::Simple => {
+            // Mirrors `parse::simple_fold`, which folds labels at compile time.
+            let fold = quote! {
+                |c: char| -> char {
+                    if c == '\u{131}' {
+                        return c;
+                    }
+                    let mut upper = c.to_uppercase();
+                    let upper = match (::core::iter::Iterator::next(&mut upper), ::core::iter::Iterator::next(&mut upper)) {
+                        (::core::option::Option::Some(upper), ::core::option::Option::None) => upper,
+                        _ => c,
+                    };
+                    let mut lower = upper.to_lowercase();
+                    match (::core::iter::Iterator::next(&mut lower), ::core::iter::Iterator::next(&mut lower)) {
+                        (::core::option::Option::Some(lower), ::core::option::Option::None) => lower,
+                        _ => upper,
+                    }
+                }
+            };
+            quote! {
+                ::core::iter::Iterator::eq(
+                    ::core::iter::Iterator::map((#a).chars(), #fold),
+                    ::core::iter::Iterator::map((#b).chars(), #fold),
+                )
+            }
+        }
+    }
+}
+
+/// Generates the length in bytes of the prefix of `s` matching `prefix`, as an `Option<usize>`,
+/// comparing under the given case folding with the `unicase` feature.
+fn prefix_len<A, B>(s: &A, prefix: &B, case_fold: CaseFold) -> proc_macro2::TokenStream
+where
+    A: ToTokens,
+    B: ToTokens,
+{
+    if !cfg!(feature = "unicase") {
+        return quote! {
+            if (#s).starts_with(#prefix) {
+                ::core::option::Option::Some((#prefix).len())
+            } else {
+                ::core::option::Option::None
+            }
+        };
+    }
+    // Folding maps each character to at least one and at most three characters, so a match ends
+    // at a single position, within three times as many characters as the prefix.
+    let eq = eq_folded(&quote! { &s[..end] }, &quote! { prefix }, case_fold);
+    quote! {
+        {
+            let (s, prefix): (&str, &str) = (#s, #prefix);
+            ::core::iter::Iterator::find(
+                &mut ::core::iter::Iterator::take(
+                    ::core::iter::Iterator::chain(
+                        ::core::iter::once(0),
+                        ::core::iter::Iterator::map(s.char_indices(), |(start, c): (usize, char)| start + c.len_utf8()),
+                    ),
+                    3 * ::core::iter::Iterator::count(prefix.chars()) + 1,
+                ),
+                |&end| #eq,
+            )
+        }
+    }
+}
+
+/// Generates a fast path for case-insensitive matching of ASCII input, which is folded once and
+/// compared to labels folded at compile time, rather than folding both sides of every comparison.
+///
+/// Input that is not ASCII falls through to comparisons under the same case folding, as does ASCII
+/// input that may still match a label whose value is unknown at compile time.
+#[cfg(feature = "unicase")]
+fn match_folded(
+    input_ident: &Ident,
+    spellings: &[(&Variant, Vec<Label>)],
+    case_fold: CaseFold,
+) -> proc_macro2::TokenStream {
+    let mut folded_spellings = Vec::<alloc::string::String>::new();
+    let mut entries = Vec::new();
+    let mut all_literal = true;
+    for (variant, spellings) in spellings {
+        for spelling in spellings {
+            let spelling = match spelling.as_lit() {
+                Some(spelling) => spelling,
+                None => {
+                    all_literal = false;
+                    continue;
+                }
+            };
+            // ASCII input folds to itself in lowercase, so it can only match ASCII spellings.
+            let folded = match case_fold {
+                CaseFold::Full => unicase::UniCase::new(spelling.value()).to_folded_case(),
+                CaseFold::Simple => spelling.value().chars().map(simple_fold).collect(),
+            };
+            if !folded.is_ascii() || folded_spellings.contains(&folded) {
+                continue;
+            }
+            entries.push((folded.clone(), *variant));
+            folded_spellings.push(folded);
+        }
+    }
+    let max_len = match folded_spellings.iter().map(|folded| folded.len()).max() {
+        Some(max_len) => max_len,
+        None => return quote! {},
+    };
+    let lookup = if cfg!(feature = "lookup_table") {
+        let table = lookup_table(
+            &quote! { folded },
+            entries
+                .iter()
+                .map(|(folded, variant)| (quote! { #folded }, *variant)),
+        );
+        quote! {
+            if let ::core::result::Result::Ok(folded) = ::core::str::from_utf8(folded) {
+                if let ::core::option::Option::Some(value) = #table {
+                    return ::core::option::Option::Some(value);
+                }
+            }
+        }
+    } else {
+        let arms = entries.iter().map(|(folded, variant)| {
+            let pattern = Literal::byte_string(folded.as_bytes());
+            let constructor = variant.constructor();
+            quote! {
+                #pattern => return ::core::option::Option::Some(Self::#constructor),
+            }
+        });
+        quote! {
+            match &*folded {
+                #(#arms)*
+                _ => (),
+            }
+        }
+    };
+    let miss = all_literal.then(|| {
+        quote! {
+            return ::core::option::Option::None;
+        }
+    });
+    quote! {
+        if #input_ident.is_ascii() {
+            if #input_ident.len() <= #max_len {
+                let mut folded = [0u8; #max_len];
+                let folded = &mut folded[..#input_ident.len()];
+                folded.copy_from_slice(#input_ident.as_bytes());
+                folded.make_ascii_lowercase();
+                #lookup
+            }
+            #miss
+        }
+    }
+}
+
+#[cfg(not(feature = "unicase"))]
+fn match_folded(_: &Ident, _: &[(&Variant, Vec<Label>)], _: CaseFold) -> proc_macro2::TokenStream {
+    quote! {}
+}
+
+/// Generates an expression looking up `key` in a hash map from spellings to variants, which is
+/// built on first use and evaluates to `Option<Self>`.
+///
+/// The map stores variant positions rather than variants, so that it does not depend on generic
+/// parameters of the enum and can live in a `static`. Earlier spellings take precedence.
+fn lookup_table<'a, I>(key: &proc_macro2::TokenStream, entries: I) -> proc_macro2::TokenStream
+where
+    I: IntoIterator<Item = (proc_macro2::TokenStream, &'a Variant)>,
+{
+    let mut variants = Vec::<&Variant>::new();
+    let mut inserts = Vec::new();
+    for (spelling, variant) in entries {
+        let index = match variants
+            .iter()
+            .position(|known| known.ident == variant.ident)
+        {
+            Some(index) => index,
+            None => {
+                variants.push(variant);
+                variants.len() - 1
+            }
+        };
+        inserts.push(quote! {
+            labels.entry(#spelling).or_insert(#index);
+        });
+    }
+    let capacity = inserts.len();
+    let indices = 0..variants.len();
+    let constructors = variants.iter().map(|variant| variant.constructor());
+    quote! {
+        {
+            static LABELS: ::std::sync::LazyLock<::std::collections::HashMap<&'static str, usize>> =
+                ::std::sync::LazyLock::new(|| {
+                    let mut labels = ::std::collections::HashMap::with_capacity(#capacity);
+                    #(#inserts)*
+                    labels
+                });
+            match LABELS.get(#key) {
+                #(::core::option::Option::Some(&#indices) => ::core::option::Option::Some(Self::#constructors),)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    }
+}
+
+/// Generates a check of each pattern variant in the given order, along with the `__glob` matcher it
+/// relies on.
+fn match_patterns(
+    input_ident: &Ident,
+    patterns: &[(&Ident, &LitStr)],
+    case_fold: CaseFold,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let string_type = string_type();
+    let (variant_idents, patterns): (Vec<&Ident>, Vec<&LitStr>) = patterns.iter().copied().unzip();
+    let segment_len = prefix_len(&quote! { &input[i..] }, &quote! { segment }, case_fold);
+    let match_patterns = quote! {
+        #(
+            if Self::__glob(#patterns, #input_ident) {
+                return ::core::option::Option::Some(Self::#variant_idents(::core::convert::From::from(<#string_type as ::core::convert::From<&str>>::from(#input_ident))));
+            }
+        )*
+    };
+    let glob = quote! {
+        /// Matches the input against a pattern in which `*` matches any run of characters and `?`
+        /// matches a single character.
+        fn __glob(pattern: &str, input: &str) -> bool {
+            let (mut p, mut i) = (0, 0);
+            // The position after the last `*` and the input it has consumed up to.
+            let mut star: ::core::option::Option<(usize, usize)> = ::core::option::Option::None;
+            loop {
+                let rest = &pattern[p..];
+                if rest.is_empty() && i == input.len() {
+                    return true;
+                }
+                // The lengths of the pattern and input consumed by a `?` or by the run of
+                // characters up to the next wildcard.
+                let next = if rest.starts_with('*') {
+                    p += 1;
+                    star = ::core::option::Option::Some((p, i));
+                    continue;
+                } else if rest.is_empty() {
+                    ::core::option::Option::None
+                } else if rest.starts_with('?') {
+                    input[i..].chars().next().map(|c| (1, c.len_utf8()))
+                } else {
+                    let segment = &rest[..rest.find(|c: char| c == '*' || c == '?').unwrap_or(rest.len())];
+                    (#segment_len).map(|len| (segment.len(), len))
+                };
+                match (next, star) {
+                    (::core::option::Option::Some((pattern_len, input_len)), _) => {
+                        p += pattern_len;
+                        i += input_len;
+                    }
+                    (::core::option::Option::None, ::core::option::Option::Some((star_p, star_i))) if star_i < input.len() => {
+                        let star_i = star_i + input[star_i..].chars().next().map_or(0, |c| c.len_utf8());
+                        p = star_p;
+                        i = star_i;
+                        star = ::core::option::Option::Some((star_p, star_i));
+                    }
+                    _ => return false,
+                }
+            }
+        }
+    };
+    (match_patterns, glob)
+}
+
+/// Generates a lookup of the aliases registered at runtime, an expression checking whether the
+/// input is one of them, and `register_alias` along with the registry it writes to.
+fn alias_registry(
+    input_ident: &Ident,
+    ident: &Ident,
+    variants: &[Variant],
+    case_fold: CaseFold,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    // Variants are stored by index, since the registry cannot name generic parameters.
+    let labeled_variants = variants
+        .iter()
+        .filter(|variant| !variant.attrs.holds_string())
+        .collect::<Vec<_>>();
+    let constructors = labeled_variants.iter().map(|variant| variant.constructor());
+    let patterns = labeled_variants.iter().map(|variant| variant.pattern());
+    let indices = 0..labeled_variants.len();
+    let held_variants = variants
+        .iter()
+        .filter(|variant| variant.attrs.holds_string())
+        .map(|variant| &variant.ident);
+    let eq = if cfg!(feature = "unicase") {
+        eq_folded(&quote! { alias }, input_ident, case_fold)
+    } else {
+        quote! { alias == #input_ident }
+    };
+    let lookup = quote! {
+        {
+            let aliases = Self::__aliases().read().unwrap_or_else(::std::sync::PoisonError::into_inner);
+            for &(alias, index) in aliases.iter() {
+                if #eq {
+                    return match index {
+                        #(#indices => ::core::option::Option::Some(Self::#constructors),)*
+                        _ => ::core::option::Option::None,
+                    };
+                }
+            }
+        }
+    };
+    let is_registered = quote! {
+        ::core::iter::Iterator::any(
+            &mut Self::__aliases().read().unwrap_or_else(::std::sync::PoisonError::into_inner).iter(),
+            |&(alias, _)| #eq,
+        )
+    };
+    let indices = 0..labeled_variants.len();
+    let registry = quote! {
+        /// Registers an alias, which is parsed as the given variant once no label has matched, so
+        /// that the accepted spellings can be extended at startup.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the variant holds the string it was parsed from.
+        pub fn register_alias(alias: &'static str, variant: Self) {
+            let index = match variant {
+                #(Self::#patterns => #indices,)*
+                #(Self::#held_variants(..) => ::core::panic!("{}::{} cannot be given aliases", ::core::stringify!(#ident), ::core::stringify!(#held_variants)),)*
+            };
+            Self::__aliases()
+                .write()
+                .unwrap_or_else(::std::sync::PoisonError::into_inner)
+                .push((alias, index));
+        }
+
+        fn __aliases() -> &'static ::std::sync::RwLock<::std::vec::Vec<(&'static str, usize)>> {
+            static ALIASES: ::std::sync::RwLock<::std::vec::Vec<(&'static str, usize)>> =
+                ::std::sync::RwLock::new(::std::vec::Vec::new());
+            &ALIASES
+        }
+    };
+    (lookup, is_registered, registry)
+}
+
+/// Generates the module given with `#[string_enum(with_module = ...)]`, whose functions serialize
+/// and deserialize the enum by its label for fields marked with `#[serde(with = ...)]`.
+fn with_module_def(vis: &Visibility, ident: &Ident, module: &Ident) -> proc_macro2::TokenStream {
+    let doc = format(format_args!(
+        "Serializes [`{ident}`] as its label, for fields marked with `#[serde(with = \"{module}\")]`."
+    ));
+    quote! {
+        #[doc = #doc]
+        #vis mod #module {
+            /// Serializes the value as its label.
+            pub fn serialize<S>(value: &super::#ident, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: ::serde::Serializer {
+                value.__serialize(serializer)
+            }
+
+            /// Deserializes a value from its label.
+            pub fn deserialize<'de, D>(deserializer: D) -> ::core::result::Result<super::#ident, D::Error> where D: ::serde::Deserializer<'de> {
+                super::#ident::__deserialize(deserializer)
+            }
+        }
+    }
+}
+
+/// Generates a test module checking that each labeled variant is parsed from the string it is
+/// formatted as, and that each alias is parsed as its variant, through the `serde_with` adapter or
+/// the `with_module` functions if the enum has them.
+fn generate_tests(
+    ident: &Ident,
+    generics: &Generics,
+    variants: &[Variant],
+    attrs: &ContainerAttrs,
+) -> proc_macro2::TokenStream {
+    let module_ident = format_ident!("__{}_string_enum_tests", ident);
+    let lifetimes = generics.lifetimes().map(|_| quote! { '_ });
+    let ty = if generics.lifetimes().next().is_some() {
+        quote! { super::#ident<#(#lifetimes),*> }
+    } else {
+        quote! { super::#ident }
+    };
+    let deserialize = match (&attrs.serde_as, &attrs.with_module) {
+        (Some(adapter), _) => {
+            quote! { <super::#adapter as ::serde_with::DeserializeAs<'_, #ty>>::deserialize_as }
+        }
+        (None, Some(_)) => quote! { super::#ident::__deserialize },
+        (None, None) => quote! { ::serde::Deserialize::deserialize },
+    };
+    // With overlapping labels, only the first variant using a spelling is parsed from it.
+    let mut claimed = Vec::new();
+    let mut claim = |label: &Label| match label.as_lit() {
+        Some(lit) if claimed.contains(&lit.value()) => false,
+        Some(lit) => {
+            claimed.push(lit.value());
+            true
+        }
+        None => true,
+    };
+    let mut label_variants = Vec::new();
+    let mut alias_variants = Vec::new();
+    let mut aliases = Vec::new();
+    for variant in variants
+        .iter()
+        .filter(|variant| !variant.attrs.holds_string())
+    {
+        if claim(variant.attrs.string.as_ref().unwrap()) {
+            label_variants.push(variant);
+        }
+        for alias in &variant.attrs.aliases {
+            if claim(alias) {
+                alias_variants.push(variant);
+                aliases.push(alias);
+            }
+        }
+    }
+    let label_names = label_variants.iter().map(|variant| &variant.ident);
+    let label_constructors = label_variants.iter().map(|variant| variant.constructor());
+    let label_patterns = label_variants.iter().map(|variant| variant.pattern());
+    let alias_names = alias_variants.iter().map(|variant| &variant.ident);
+    let alias_patterns = alias_variants.iter().map(|variant| variant.pattern());
+    let parses_aliases = (!aliases.is_empty()).then(|| {
+        quote! {
+            #[::core::prelude::v1::test]
+            fn parses_aliases() {
+                #(
+                    ::core::assert!(
+                        ::core::matches!(deserialize(#aliases), ::core::result::Result::Ok(super::#ident::#alias_patterns)),
+                        "{:?} is not parsed as {}",
+                        #aliases,
+                        ::core::stringify!(#alias_names),
+                    );
+                )*
+            }
+        }
+    });
+    quote! {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod #module_ident {
+            extern crate std;
+
+            fn deserialize(s: &str) -> ::core::result::Result<#ty, ::serde::de::value::Error> {
+                #deserialize(::serde::de::value::BorrowedStrDeserializer::new(s))
+            }
+
+            #[::core::prelude::v1::test]
+            fn round_trips_labels() {
+                #(
+                    let label = self::std::string::ToString::to_string(&super::#ident::#label_constructors);
+                    ::core::assert!(
+                        ::core::matches!(deserialize(&label), ::core::result::Result::Ok(super::#ident::#label_patterns)),
+                        "{} is not parsed from {:?}",
+                        ::core::stringify!(#label_names),
+                        label,
+                    );
+                )*
+            }
+
+            #parses_aliases
+        }
+    }
+}
+
+/// Generates `__suggest`, which returns the label or alias closest to the input by edit distance,
+/// ignoring ASCII case, if it is close enough to be a likely typo.
+fn suggest<'a, I>(labels: I) -> proc_macro2::TokenStream
+where
+    I: IntoIterator<Item = &'a Label>,
+{
+    let vec_type = vec_type();
+    let labels = labels.into_iter();
+    quote! {
+        fn __suggest(input: &str) -> ::core::option::Option<&'static str> {
+            let input = ::core::iter::Iterator::collect::<#vec_type<char>>(input.chars());
+            let mut best: ::core::option::Option<(usize, &'static str)> = ::core::option::Option::None;
+            let mut distances = #vec_type::<usize>::new();
+            let labels: &[&'static str] = &[#(#labels),*];
+            for &label in labels {
+                // Optimal string alignment distance, which counts swapping adjacent characters as
+                // a single edit.
+                let chars = ::core::iter::Iterator::collect::<#vec_type<char>>(label.chars());
+                let width = chars.len() + 1;
+                distances.clear();
+                distances.resize((input.len() + 1) * width, 0);
+                for i in 0..=input.len() {
+                    distances[i * width] = i;
+                }
+                for j in 0..=chars.len() {
+                    distances[j] = j;
+                }
+                for i in 1..=input.len() {
+                    for j in 1..=chars.len() {
+                        let cost = if input[i - 1].eq_ignore_ascii_case(&chars[j - 1]) { 0 } else { 1 };
+                        let mut distance = ::core::cmp::min(
+                            ::core::cmp::min(distances[(i - 1) * width + j] + 1, distances[i * width + j - 1] + 1),
+                            distances[(i - 1) * width + j - 1] + cost,
+                        );
+                        if i > 1
+                            && j > 1
+                            && input[i - 1].eq_ignore_ascii_case(&chars[j - 2])
+                            && input[i - 2].eq_ignore_ascii_case(&chars[j - 1])
+                        {
+                            distance = ::core::cmp::min(distance, distances[(i - 2) * width + j - 2] + 1);
+                        }
+                        distances[i * width + j] = distance;
+                    }
+                }
+                let distance = distances[input.len() * width + chars.len()];
+                let closer = match best {
+                    ::core::option::Option::Some((best_distance, _)) => distance < best_distance,
+                    ::core::option::Option::None => true,
+                };
+                if closer && distance <= ::core::cmp::max(1, chars.len() / 3) {
+                    best = ::core::option::Option::Some((distance, label));
+                }
+            }
+            best.map(|(_, label)| label)
+        }
+    }
+}
+
+/// Returns the path to the owned string type, which is only available with an allocator.
+fn string_type() -> proc_macro2::TokenStream {
+    if cfg!(feature = "std") {
+        quote! { ::std::string::String }
+    } else {
+        quote! { ::alloc::string::String }
+    }
+}
+
+/// Generates a `tracing` warning that the unknown string in `value` is parsed as the `default` or
+/// `other` variant, if the `tracing` feature is enabled.
+fn trace_fallback(ident: &Ident, value: &Ident) -> proc_macro2::TokenStream {
+    if !cfg!(feature = "tracing") {
+        return quote! {};
+    }
+    quote! {
+        {
+            // Long input is cut at a character boundary, so that events stay small.
+            let end = ::core::iter::Iterator::nth(&mut #value.char_indices(), 64).map_or(#value.len(), |(i, _)| i);
+            ::tracing::warn!(enum_name = ::core::stringify!(#ident), value = &#value[..end], "unknown label parsed as fallback variant");
+        }
+    }
+}
+
+/// Returns the expression constructing the variant named by a container attribute, with any fields
+/// set to their default values.
+fn constructor_of(variants: &[Variant], variant_ident: &Ident) -> proc_macro2::TokenStream {
+    variants
+        .iter()
+        .find(|variant| variant.ident == *variant_ident)
+        .map_or_else(|| quote! { #variant_ident }, Variant::constructor)
+}
+
+/// Returns the path to the `Vec` type, which is only available with an allocator.
+fn vec_type() -> proc_macro2::TokenStream {
+    if cfg!(feature = "std") {
+        quote! { ::std::vec::Vec }
+    } else {
+        quote! { ::alloc::vec::Vec }
+    }
+}
+
+/// Returns the path to the `Cow` type, which is only available with an allocator.
+fn cow_type() -> proc_macro2::TokenStream {
+    if cfg!(feature = "std") {
+        quote! { ::std::borrow::Cow }
+    } else {
+        quote! { ::alloc::borrow::Cow }
+    }
+}
+
+/// Returns the path to the `ToString` trait, which is only available with an allocator.
+fn to_string_trait() -> proc_macro2::TokenStream {
+    if cfg!(feature = "std") {
+        quote! { ::std::string::ToString }
+    } else {
+        quote! { ::alloc::string::ToString }
+    }
+}
+
+/// Generates `description()`, which returns the doc comment of each variant, if requested with
+/// `#[string_enum(description)]`.
+fn description(input: &LabeledStringInput) -> proc_macro2::TokenStream {
+    if !input.attrs.description {
+        return quote! {};
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let match_variants = input.variants.iter().map(|variant| {
+        let lines = variant
+            .attrs
+            .docs
+            .iter()
+            .map(|doc| {
+                let line = doc.value();
+                let line = line.strip_prefix(' ').unwrap_or(&line);
+                alloc::string::String::from(line.trim_end())
+            })
+            .collect::<Vec<_>>();
+        let description = lines.join("\n");
+        let description = description.trim_matches('\n');
+        let pattern = variant.pattern();
+        quote! {
+            Self::#pattern => #description,
+        }
+    });
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the doc comment of the variant, or an empty string if it has none.
+            pub fn description(&self) -> &'static str {
+                match self {
+                    #(#match_variants)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `label_id()`, `from_label_id()`, `index()`, and `from_index()`, which convert between
+/// variants and their ids or positions, for enums without an `other` or `pattern` variant.
+fn variant_ids(input: &LabeledStringInput) -> proc_macro2::TokenStream {
+    if input
+        .variants
+        .iter()
+        .any(|variant| variant.attrs.holds_string())
+    {
+        return quote! {};
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let patterns = input
+        .variants
+        .iter()
+        .map(|variant| variant.pattern())
+        .collect::<Vec<_>>();
+    let constructors = input
+        .variants
+        .iter()
+        .map(|variant| variant.constructor())
+        .collect::<Vec<_>>();
+    // Default values cannot be constructed in a constant context.
+    let constness = input
+        .variants
+        .iter()
+        .all(|variant| !variant.has_default_fields())
+        .then(|| quote! { const });
+    let ids = input
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(i, variant)| {
+            let id = variant.attrs.id.map_or(i as u32, |(_, id)| id);
+            Literal::u32_unsuffixed(id)
+        })
+        .collect::<Vec<_>>();
+    let indices = 0..patterns.len();
+    let from_indices = 0..patterns.len();
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the position of the variant in declaration order.
+            pub const fn index(&self) -> usize {
+                match self {
+                    #(Self::#patterns => #indices,)*
+                }
+            }
+
+            /// Returns the variant at the given position in declaration order, if any.
+            pub #constness fn from_index(index: usize) -> ::core::option::Option<Self> {
+                match index {
+                    #(#from_indices => ::core::option::Option::Some(Self::#constructors),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            /// Returns the stable id of the variant, which is its position in declaration order
+            /// unless given with `#[string(id = N)]`.
+            pub const fn label_id(&self) -> u32 {
+                match self {
+                    #(Self::#patterns => #ids,)*
+                }
+            }
+
+            /// Returns the variant with the given id, if any.
+            pub #constness fn from_label_id(id: u32) -> ::core::option::Option<Self> {
+                match id {
+                    #(#ids => ::core::option::Option::Some(Self::#constructors),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    }
+}
+
+/// Generates `label_bytes()`, which returns the label of each variant as bytes, for enums without
+/// an `other` or `pattern` variant.
+fn label_bytes(input: &LabeledStringInput) -> proc_macro2::TokenStream {
+    if input
+        .variants
+        .iter()
+        .any(|variant| variant.attrs.holds_string())
+    {
+        return quote! {};
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let match_variants = input.variants.iter().map(|variant| {
+        let pattern = variant.pattern();
+        let bytes = match variant.attrs.string.as_ref().unwrap() {
+            label @ Label::Bytes(_) => quote! { #label },
+            label => quote! { #label.as_bytes() },
+        };
+        quote! {
+            Self::#pattern => #bytes,
+        }
+    });
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the label of the variant as bytes.
+            pub const fn label_bytes(&self) -> &'static [u8] {
+                match self {
+                    #(#match_variants)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `__LABELS`, a hidden constant listing the label of each labeled variant, which other
+/// enums check against with `#[string_enum(subset_of = ...)]`.
+fn label_table(input: &LabeledStringInput) -> proc_macro2::TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let labels = input
+        .variants
+        .iter()
+        .filter_map(|variant| variant.attrs.string.as_ref());
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            pub const __LABELS: &'static [&'static str] = &[#(#labels),*];
+        }
+    }
+}
+
+/// Generates the constants requested with `#[string_enum(label_constants)]`, holding the label of
+/// each labeled variant and named after the variant in screaming snake case, as in `GRASS_LABEL`.
+fn label_constants(input: &LabeledStringInput) -> proc_macro2::TokenStream {
+    if !input.attrs.label_constants {
+        return quote! {};
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let constants = input.variants.iter().filter_map(|variant| {
+        let variant_ident = &variant.ident;
+        let label = variant.attrs.string.as_ref()?;
+        let constant = variant.label_constant();
+        let doc = format(format_args!("The label of [`{ident}::{variant_ident}`]."));
+        Some(quote! {
+            #[doc = #doc]
+            pub const #constant: &'static str = #label;
+        })
+    });
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#constants)*
+        }
+    }
+}
+
+/// Generates the C functions exported with `#[string_enum(c_ffi = "...")]`, which convert between
+/// variant indices and NUL-terminated labels.
+fn c_ffi(input: &LabeledStringInput) -> proc_macro2::TokenStream {
+    let prefix = match &input.attrs.c_ffi {
+        Some(prefix) => prefix.value(),
+        None => return quote! {},
+    };
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let name = screaming_snake_case(&ident.unraw()).to_lowercase();
+    let label_fn = format_ident!("{prefix}{name}_label");
+    let from_label_fn = format_ident!("{prefix}{name}_from_label");
+    let labels = input.variants.iter().filter_map(|variant| {
+        let label = variant.attrs.string.as_ref()?.as_lit()?;
+        Some(LitStr::new(
+            &format(format_args!("{}\0", label.value())),
+            label.span(),
+        ))
+    });
+    let patterns = input.variants.iter().map(|variant| variant.pattern());
+    let indices = (0..input.variants.len() as i32).map(Literal::i32_unsuffixed);
+    let label_doc = format(format_args!(
+        "Returns the NUL-terminated label of the [`{ident}`] variant at the given index, or null \
+         if there is none."
+    ));
+    let from_label_doc = format(format_args!(
+        "Returns the index of the [`{ident}`] variant parsed from the NUL-terminated string, or \
+         -1 if it does not parse."
+    ));
+    quote! {
+        #[doc = #label_doc]
+        #[no_mangle]
+        #vis extern "C" fn #label_fn(v: u32) -> *const ::core::ffi::c_char {
+            const LABELS: &[&str] = &[#(#labels),*];
+            match LABELS.get(v as usize) {
+                ::core::option::Option::Some(label) => label.as_ptr().cast(),
+                ::core::option::Option::None => ::core::ptr::null(),
+            }
+        }
+
+        #[doc = #from_label_doc]
+        ///
+        /// # Safety
+        ///
+        /// `label` must be null or point to a NUL-terminated string.
+        #[no_mangle]
+        #vis unsafe extern "C" fn #from_label_fn(label: *const ::core::ffi::c_char) -> i32 {
+            if label.is_null() {
+                return -1;
+            }
+            let label = match ::core::ffi::CStr::from_ptr(label).to_str() {
+                ::core::result::Result::Ok(label) => label,
+                ::core::result::Result::Err(_) => return -1,
+            };
+            match <#ident as ::core::str::FromStr>::from_str(label) {
+                #(::core::result::Result::Ok(#ident::#patterns) => #indices,)*
+                ::core::result::Result::Err(_) => -1,
+            }
+        }
+    }
+}
+
+/// Generates a constant assertion that each label of the enum is also a label of the enum given
+/// with `#[string_enum(subset_of = ...)]`, failing compilation when the two drift apart.
+fn subset_of(input: &LabeledStringInput) -> proc_macro2::TokenStream {
+    let full = match &input.attrs.subset_of {
+        Some(full) => full,
+        None => return quote! {},
+    };
+    let ident = &input.ident;
+    let full_name = full
+        .segments
+        .iter()
+        .map(|segment| format(format_args!("{}", segment.ident)))
+        .collect::<Vec<_>>()
+        .join("::");
+    let assertions = input.variants.iter().filter_map(|variant| {
+        let variant_ident = &variant.ident;
+        let label = variant.attrs.string.as_ref()?;
+        let message = format(format_args!(
+            "the label of {ident}::{variant_ident} is not a label of {full_name}"
+        ));
+        Some(quote_spanned! { variant_ident.span()=>
+            ::core::assert!(contains(<#full>::__LABELS, #label), #message);
+        })
+    });
+    quote! {
+        const _: () = {
+            const fn contains(labels: &[&str], label: &str) -> bool {
+                let label = label.as_bytes();
+                let mut i = 0;
+                while i < labels.len() {
+                    let candidate = labels[i].as_bytes();
+                    if candidate.len() == label.len() {
+                        let mut j = 0;
+                        while j < label.len() && candidate[j] == label[j] {
+                            j += 1;
+                        }
+                        if j == label.len() {
+                            return true;
+                        }
+                    }
+                    i += 1;
+                }
+                false
+            }
+            #(#assertions)*
+        };
+    }
+}
+
+/// Returns the `#[inline]` attribute for generated functions on the hot path, if requested with
+/// `#[string_enum(inline)]`.
+fn inline_hint(attrs: &ContainerAttrs) -> Option<proc_macro2::TokenStream> {
+    attrs.inline.then(|| quote! { #[inline] })
+}
+
+/// Maximum number of variants handled by a single function generated for a labeled enum.
+///
+/// Larger enums are split into helper functions of this many variants each, since a single
+/// function with hundreds of branches is slow to compile.
+const CHUNK_SIZE: usize = 64;
+
+/// Maximum number of bytes of the offending input kept by parse errors when no allocator is
+/// available.
+const ERROR_PREFIX_LEN: usize = 16;
+
+/// Generates the error type returned by the `FromStr` implementation of a labeled enum.
+///
+/// With an allocator, the error owns a copy of the offending input. Otherwise, it records the
+/// input's length and as much of its prefix as fits in a fixed-size buffer.
+///
+/// A suggestion is looked up with the error's own copy of `__suggest`, since the error cannot name
+/// the generic parameters of the enum.
+fn labeled_parse_error(
+    vis: &Visibility,
+    ident: &Ident,
+    error_ident: &Ident,
+    suggest: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let doc = format(format_args!(
+        "Error returned when a string does not match any label of [`{ident}`]."
+    ));
+    if cfg!(any(feature = "std", feature = "alloc")) {
+        let string_type = string_type();
+        let error_impl = if cfg!(feature = "std") {
+            quote! {
+                impl ::std::error::Error for #error_ident {}
+            }
+        } else {
+            quote! {}
+        };
+        let suggest_impl = suggest.map(|suggest| {
+            quote! {
+                impl #error_ident {
+                    #suggest
+                }
+            }
+        });
+        let display = if suggest.is_some() {
+            quote! {
+                match Self::__suggest(&self.input) {
+                    ::core::option::Option::Some(suggestion) => f.write_fmt(::core::format_args!("invalid {}: {}, did you mean {:?}?", ::core::stringify!(#ident), self.input, suggestion)),
+                    ::core::option::Option::None => f.write_fmt(::core::format_args!("invalid {}: {}", ::core::stringify!(#ident), self.input)),
+                }
+            }
+        } else {
+            quote! {
+                f.write_fmt(::core::format_args!("invalid {}: {}", ::core::stringify!(#ident), self.input))
+            }
+        };
+        quote! {
+            #[doc = #doc]
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            #vis struct #error_ident {
+                /// The input that failed to parse.
+                pub input: #string_type,
+            }
+
+            #suggest_impl
+
+            impl ::core::fmt::Display for #error_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #display
+                }
+            }
+
+            #error_impl
+
+            // Keeps callers that propagated the message from when `from_str` failed with a string.
+            impl ::core::convert::From<#error_ident> for #string_type {
+                fn from(error: #error_ident) -> Self {
+                    let mut message = #string_type::new();
+                    let _ = ::core::fmt::Write::write_fmt(&mut message, ::core::format_args!("{}", error));
+                    message
+                }
+            }
+        }
+    } else {
+        let prefix_doc = format(format_args!(
+            "Returns the beginning of the input that failed to parse, truncated to at most \
+             {ERROR_PREFIX_LEN} bytes."
+        ));
+        // The message is built here, since formatting it at runtime would need an allocator.
+        let message = LitStr::new(&format(format_args!("invalid {ident}")), ident.span());
+        quote! {
+            #[doc = #doc]
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::marker::Copy, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            #vis struct #error_ident {
+                /// The length, in bytes, of the input that failed to parse.
+                pub input_len: usize,
+                prefix: [u8; #ERROR_PREFIX_LEN],
+                prefix_len: usize,
+            }
+
+            impl #error_ident {
+                fn new(input: &str) -> Self {
+                    let mut prefix_len = ::core::cmp::min(input.len(), #ERROR_PREFIX_LEN);
+                    while !input.is_char_boundary(prefix_len) {
+                        prefix_len -= 1;
+                    }
+                    let mut prefix = [0; #ERROR_PREFIX_LEN];
+                    prefix[..prefix_len].copy_from_slice(&input.as_bytes()[..prefix_len]);
+                    Self {
+                        input_len: input.len(),
+                        prefix,
+                        prefix_len,
+                    }
+                }
+
+                #[doc = #prefix_doc]
+                pub fn input_prefix(&self) -> &str {
+                    ::core::str::from_utf8(&self.prefix[..self.prefix_len]).unwrap_or_default()
+                }
+            }
+
+            impl ::core::fmt::Display for #error_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(#message)
+                }
+            }
+        }
+    }
+}
+
+/// Procedural macro for deserializing strings to enum variants, where each variant is labeled with
+/// a `#[string = ...]` attribute.
+///
+/// Also implements [`core::str::FromStr`], which fails with a generated `{Enum}ParseError` type.
+/// See the [crate documentation](crate#deserializing) for the other generated items and the
+/// container attributes.
+#[proc_macro_derive(
+    DeserializeLabeledStringEnum,
+    attributes(string, alias, number, string_enum)
+)]
+pub fn derive_labeled_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as LabeledStringInput);
+    if input.byte_labels {
+        return bytes::derive_deserialize(input);
+    }
+    let c_ffi = c_ffi(&input);
+    let call_site = Span::call_site();
+    let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let de_generics = de_generics(&input.generics);
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+    let visitor_ident = Ident::new(&format(format_args!("{ident}Visitor")), call_site);
+    let input_ident = Ident::new("s", call_site);
+
+    // Every spelling accepted for each labeled variant, in the order they are checked.
+    let spellings = input
+        .variants
+        .iter()
+        .filter(|variant| !variant.attrs.holds_string())
+        .map(|variant| {
+            let string = variant.attrs.string.as_ref().unwrap();
+            let mut spellings = vec![string.clone()];
+            spellings.extend(
+                input
+                    .attrs
+                    .serialize_case
+                    .zip(string.as_lit())
+                    .map(|(case, string)| case.apply(string))
+                    .filter(|serialized| {
+                        Some(serialized.value()) != string.as_lit().map(LitStr::value)
+                    })
+                    .map(Label::Lit),
+            );
+            spellings.extend(variant.attrs.aliases.iter().cloned());
+            if input.attrs.percent_decode {
+                let encodings = core::iter::once(string)
+                    .chain(&variant.attrs.aliases)
+                    .filter_map(Label::as_lit)
+                    .flat_map(|label| percent::encodings(&label.value()))
+                    .collect::<Vec<_>>();
+                spellings.extend(
+                    encodings
+                        .iter()
+                        .map(|encoding| Label::Lit(LitStr::new(encoding, call_site))),
+                );
+            }
+            (variant, spellings)
+        })
+        .collect::<Vec<_>>();
+    let match_variants = spellings
+        .iter()
+        .map(|(variant, spellings)| {
+            let constructor = variant.constructor();
+            let matches = spellings
+                .iter()
+                .map(|spelling| matches_spelling(spelling, input.attrs.case_fold));
+            quote! {
+                #(
+                    if #matches {
+                        return ::core::option::Option::Some(Self::#constructor)
+                    }
+                )*
+            }
+        })
+        .collect::<Vec<_>>();
+    let match_folded = match_folded(&input_ident, &spellings, input.attrs.case_fold);
+    let unicase_input = wrap_unicase(&input_ident, input.attrs.case_fold);
+    let mut from_label_chunks = Vec::new();
+    let from_label = if cfg!(feature = "lookup_table") && !cfg!(feature = "unicase") {
+        // Without case folding, the lookup table holds every spelling and replaces the comparisons,
+        // taking precedence over the runtime crate's linear search.
+        lookup_table(
+            &quote! { #input_ident },
+            spellings.iter().flat_map(|(variant, spellings)| {
+                spellings
+                    .iter()
+                    .map(move |spelling| (quote! { #spelling }, *variant))
+            }),
+        )
+    } else if cfg!(feature = "runtime") {
+        // Matches are found by a function shared by every enum in the runtime crate, given the
+        // comparison folding case with the `unicase` feature.
+        let labels = spellings
+            .iter()
+            .flat_map(|(_, spellings)| spellings)
+            .collect::<Vec<_>>();
+        let label_count = labels.len();
+        let mut start = 0;
+        let arms = spellings.iter().map(|(variant, spellings)| {
+            let positions = start..start + spellings.len();
+            start += spellings.len();
+            let constructor = variant.constructor();
+            quote! {
+                #(::core::option::Option::Some(#positions))|* => ::core::option::Option::Some(Self::#constructor),
+            }
+        });
+        let position = if cfg!(feature = "unicase") {
+            let eq = eq_folded(&quote! { label }, &quote! { input }, input.attrs.case_fold);
+            quote! {
+                ::serde_string_enum_runtime::label::position_by(&LABELS, #input_ident, |label, input| #eq)
+            }
+        } else {
+            quote! { ::serde_string_enum_runtime::label::position(&LABELS, #input_ident) }
+        };
+        quote! {
+            #match_folded
+            static LABELS: [&str; #label_count] = [#(#labels),*];
+            match #position {
+                #(#arms)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    } else if match_variants.len() > CHUNK_SIZE {
+        // Compare against each chunk of the variants in a separate helper, in order.
+        let chunk_idents = (0..match_variants.len().div_ceil(CHUNK_SIZE))
+            .map(|i| format_ident!("__from_label_{}", i))
+            .collect::<Vec<_>>();
+        from_label_chunks = chunk_idents
+            .iter()
+            .zip(match_variants.chunks(CHUNK_SIZE))
+            .map(|(chunk_ident, chunk)| {
+                quote! {
+                    fn #chunk_ident(#input_ident: &str) -> ::core::option::Option<Self> {
+                        let value = #unicase_input;
+                        #(#chunk)*
+                        ::core::option::Option::None
+                    }
+                }
+            })
+            .collect();
+        quote! {
+            #match_folded
+            #(
+                if let ::core::option::Option::Some(value) = Self::#chunk_idents(#input_ident) {
+                    return ::core::option::Option::Some(value);
+                }
+            )*
+            ::core::option::Option::None
+        }
+    } else {
+        quote! {
+            #match_folded
+            let value = #unicase_input;
+            #(#match_variants)*
+            ::core::option::Option::None
+        }
+    };
+    let strip_qualifier = input.attrs.qualifier(&ident).map(|qualifier| {
+        quote! {
+            let #input_ident = #input_ident.strip_prefix(#qualifier).unwrap_or(#input_ident);
+        }
+    });
+    let mut patterns = input
+        .variants
+        .iter()
+        .filter(|variant| variant.attrs.pattern.is_some())
+        .collect::<Vec<_>>();
+    // The sort is stable, so patterns of equal priority keep their declaration order.
+    patterns.sort_by_key(|variant| {
+        core::cmp::Reverse(variant.attrs.priority.map_or(0, |(_, priority)| priority))
+    });
+    let patterns = patterns
+        .into_iter()
+        .map(|variant| (&variant.ident, variant.attrs.pattern.as_ref().unwrap()))
+        .collect::<Vec<_>>();
+
+    let inline = inline_hint(&input.attrs);
+    // Registered aliases, pattern variants, and then the catch-all function are only tried once the
+    // exact labels have failed to match, and the unknown string is observed once all have failed.
+    let mut fallbacks = Vec::new();
+    let mut fallback_fns = Vec::new();
+    let mut is_registered = None;
+    if cfg!(feature = "alias_registry")
+        && input
+            .variants
+            .iter()
+            .any(|variant| !variant.attrs.holds_string())
+    {
+        let (lookup, is_registered_alias, registry) =
+            alias_registry(&input_ident, &ident, &input.variants, input.attrs.case_fold);
+        is_registered = Some(is_registered_alias);
+        fallbacks.push(lookup);
+        fallback_fns.push(registry);
+    }
+    if !patterns.is_empty() {
+        let (match_patterns, glob) = match_patterns(&input_ident, &patterns, input.attrs.case_fold);
+        fallbacks.push(match_patterns);
+        fallback_fns.push(glob);
+    }
+    if let Some(catch_all_with) = &input.attrs.catch_all_with {
+        fallbacks.push(quote! {
+            if let ::core::option::Option::Some(value) = #catch_all_with(#input_ident) {
+                return ::core::option::Option::Some(value);
+            }
+        });
+    }
+    if let Some(on_unknown) = &input.attrs.on_unknown {
+        fallbacks.push(quote! {
+            #on_unknown(#input_ident);
+        });
+    }
+    // Only the labels and aliases themselves are checked, without the remaining fallbacks.
+    let is_label = if fallbacks.is_empty() {
+        quote! { Self::__from_label(#input_ident).is_some() }
+    } else {
+        let is_registered = is_registered.map(|is_registered| quote! { || #is_registered });
+        quote! {
+            #strip_qualifier
+            Self::__from_exact_label(#input_ident).is_some() #is_registered
+        }
+    };
+    let matches = quote! {
+        /// Returns whether the string is a label or alias of the enum, without constructing a
+        /// parse error, for validating strings that are not otherwise needed as the enum.
+        #inline
+        pub fn matches(#input_ident: &str) -> bool {
+            #is_label
+        }
+    };
+    let accepted_strings = input.variants.iter().flat_map(|variant| {
+        let cased = variant.attrs.string.as_ref().and_then(|string| {
+            let string = string.as_lit()?;
+            let cased = input.attrs.serialize_case?.apply(string);
+            (cased.value() != string.value()).then_some(Label::Lit(cased))
+        });
+        variant
+            .attrs
+            .string
+            .iter()
+            .cloned()
+            .chain(cased)
+            .chain(variant.attrs.aliases.iter().cloned())
+    });
+    let all_accepted_strings = quote! {
+        /// Returns every label and alias of the enum, in declaration order, for building
+        /// validation patterns, documentation, and schemas that must accept the same strings.
+        pub fn all_accepted_strings() -> impl ::core::iter::Iterator<Item = &'static str> {
+            ::core::iter::IntoIterator::into_iter([#(#accepted_strings),*])
+        }
+    };
+    let from_label = if fallbacks.is_empty() {
+        quote! {
+            #inline
+            fn __from_label(#input_ident: &str) -> ::core::option::Option<Self> {
+                #strip_qualifier
+                #from_label
+            }
+        }
+    } else {
+        quote! {
+            fn __from_label(#input_ident: &str) -> ::core::option::Option<Self> {
+                #strip_qualifier
+                if let ::core::option::Option::Some(value) = Self::__from_exact_label(#input_ident) {
+                    return ::core::option::Option::Some(value);
+                }
+                #(#fallbacks)*
+                ::core::option::Option::None
+            }
+
+            #inline
+            fn __from_exact_label(#input_ident: &str) -> ::core::option::Option<Self> {
+                #from_label
+            }
+
+            #(#fallback_fns)*
+        }
+    };
+
+    let has_alloc = cfg!(any(feature = "std", feature = "alloc"));
+    let string_type = string_type();
+    let other = input.variants.iter().find(|variant| variant.attrs.other);
+    // Whether the "other" variant can take ownership of a string that does not outlive the input.
+    let other_is_owned = other.is_some_and(|variant| {
+        has_alloc
+            && !variant
+                .fields
+                .iter()
+                .any(|field| matches!(field.ty, Type::Reference(_)))
+    });
+
+    // Unknown strings are only reported when there is no variant to fall back to.
+    let suggestions =
+        cfg!(feature = "suggestions") && other.is_none() && input.attrs.default.is_none();
+    let completions = input.attrs.completions.map(|completions| {
+        let labels = input.variants.iter().flat_map(|variant| {
+            let aliases = match completions {
+                Completions::Labels => &[][..],
+                Completions::Aliases => &variant.attrs.aliases[..],
+            };
+            variant.attrs.string.iter().chain(aliases)
+        });
+        let starts_with = prefix_len(&quote! { label }, &quote! { prefix }, input.attrs.case_fold);
+        quote! {
+            /// Returns the labels that start with the given prefix, in declaration order, for
+            /// completing partial input.
+            pub fn completions(prefix: &str) -> impl ::core::iter::Iterator<Item = &'static str> + '_ {
+                ::core::iter::Iterator::filter(
+                    ::core::iter::IntoIterator::into_iter([#(#labels),*]),
+                    move |label: &&'static str| (#starts_with).is_some(),
+                )
+            }
+        }
+    });
+    let parse_list = input.attrs.list_delimiter.as_ref().map(|delimiter| {
+        let vec_type = vec_type();
+        quote! {
+            /// Parses a list of labels separated by the list delimiter, ignoring whitespace around
+            /// each label. An empty string is an empty list.
+            pub fn parse_list(#input_ident: &str) -> ::core::result::Result<#vec_type<Self>, <Self as ::core::str::FromStr>::Err> {
+                if #input_ident.trim().is_empty() {
+                    return ::core::result::Result::Ok(#vec_type::new());
+                }
+                ::core::iter::Iterator::collect(::core::iter::Iterator::map(
+                    #input_ident.split(#delimiter),
+                    |item| <Self as ::core::str::FromStr>::from_str(item.trim()),
+                ))
+            }
+        }
+    });
+    let (parse_detailed, match_kind) = input
+        .attrs
+        .parse_detailed
+        .map(|_| {
+            let kind_ident = Ident::new(&format(format_args!("{ident}MatchKind")), call_site);
+            let arms = input
+                .variants
+                .iter()
+                .filter(|variant| !variant.attrs.holds_string())
+                .map(|variant| {
+                    let pattern = variant.pattern();
+                    let string = variant.attrs.string.as_ref().unwrap();
+                    let serialized = input
+                        .attrs
+                        .serialize_case
+                        .zip(string.as_lit())
+                        .map(|(case, string)| case.apply(string))
+                        .map(|serialized| quote! { || #input_ident == #serialized });
+                    let aliases = &variant.attrs.aliases;
+                    let alias_indices = 0..aliases.len();
+                    let folded = cfg!(feature = "unicase").then(|| {
+                        let matches = core::iter::once(string)
+                            .chain(aliases)
+                            .map(|spelling| eq_folded(&input_ident, spelling, input.attrs.case_fold));
+                        quote! {
+                            else if #(#matches)||* {
+                                #kind_ident::CaseFolded
+                            }
+                        }
+                    });
+                    quote! {
+                        Self::#pattern => {
+                            if #input_ident == #string #serialized {
+                                #kind_ident::Label
+                            }
+                            #(
+                                else if #input_ident == #aliases {
+                                    #kind_ident::Alias(#alias_indices)
+                                }
+                            )*
+                            #folded
+                            else {
+                                #kind_ident::Other
+                            }
+                        }
+                    }
+                });
+            let held = input
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.holds_string())
+                .then(|| quote! { _ => #kind_ident::Other, });
+            let doc = format(format_args!(
+                "How a string was matched by [`{ident}::parse_detailed`]."
+            ));
+            let vis = &input.vis;
+            let match_kind = quote! {
+                #[doc = #doc]
+                #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::marker::Copy, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+                #vis enum #kind_ident {
+                    /// The label of the variant, as it is serialized.
+                    Label,
+                    /// The alias at the given position among the aliases of the variant.
+                    Alias(usize),
+                    /// A label or alias spelled in a different case, with the `unicase` feature.
+                    CaseFolded,
+                    /// Any other accepted string, such as one held by the `other` variant.
+                    Other,
+                }
+            };
+            let parse_detailed = quote! {
+                /// Parses the string like `FromStr`, also reporting which spelling it matched, so
+                /// that non-canonical spellings can be flagged.
+                pub fn parse_detailed(#input_ident: &str) -> ::core::result::Result<(Self, #kind_ident), <Self as ::core::str::FromStr>::Err> {
+                    let value = <Self as ::core::str::FromStr>::from_str(#input_ident)?;
+                    #strip_qualifier
+                    let kind = match &value {
+                        #(#arms)*
+                        #held
+                    };
+                    ::core::result::Result::Ok((value, kind))
+                }
+            };
+            (parse_detailed, match_kind)
+        })
+        .unzip();
+    // The `nom` parser shares the longest-prefix matching of `parse_prefix`, whether or not it is
+    // public.
+    let prefix_ident = if input.attrs.parse_prefix {
+        format_ident!("parse_prefix")
+    } else {
+        format_ident!("__parse_prefix")
+    };
+    let parse_prefix = (input.attrs.parse_prefix || cfg!(feature = "nom")).then(|| {
+        let (constructors, spellings): (Vec<proc_macro2::TokenStream>, Vec<&Label>) = spellings
+            .iter()
+            .flat_map(|(variant, spellings)| {
+                spellings
+                    .iter()
+                    .map(move |spelling| (variant.constructor(), spelling))
+            })
+            .unzip();
+        let label_len = prefix_len(&input_ident, &quote! { label }, input.attrs.case_fold);
+        let vis = input.attrs.parse_prefix.then(|| {
+            quote! {
+                /// Parses the longest label at the start of the input, returning the variant and the
+                /// remaining input.
+                pub
+            }
+        });
+        quote! {
+            #vis fn #prefix_ident(#input_ident: &str) -> ::core::option::Option<(Self, &str)> {
+                #strip_qualifier
+                let mut longest: ::core::option::Option<(Self, usize)> = ::core::option::Option::None;
+                #(
+                    let label: &str = #spellings;
+                    if let ::core::option::Option::Some(len) = #label_len {
+                        if longest.as_ref().map_or(true, |(_, longest)| len > *longest) {
+                            longest = ::core::option::Option::Some((Self::#constructors, len));
+                        }
+                    }
+                )*
+                longest.map(|(value, len)| (value, &#input_ident[len..]))
+            }
+        }
+    });
+    let nom = cfg!(feature = "nom").then(|| {
+        quote! {
+            /// Parses the longest label or alias at the start of the input, for embedding the enum
+            /// in `nom` grammars.
+            pub fn parser(#input_ident: &str) -> ::nom::IResult<&str, Self> {
+                match Self::#prefix_ident(#input_ident) {
+                    ::core::option::Option::Some((value, rest)) => ::core::result::Result::Ok((rest, value)),
+                    ::core::option::Option::None => ::core::result::Result::Err(::nom::Err::Error(
+                        ::nom::error::Error::new(#input_ident, ::nom::error::ErrorKind::Tag),
+                    )),
+                }
+            }
+        }
+    });
+    let suggest_fn = suggestions.then(|| {
+        suggest(
+            input
+                .variants
+                .iter()
+                .flat_map(|variant| variant.attrs.string.iter().chain(&variant.attrs.aliases)),
+        )
+    });
+    let suggest = suggest_fn.as_ref().map(|suggest| {
+        quote! {
+            #suggest
+
+            /// Parses the string, or the label or alias closest to it by edit distance if it is
+            /// close enough to be a likely typo.
+            pub fn closest_match(#input_ident: &str) -> ::core::option::Option<Self> {
+                Self::__from_label(#input_ident)
+                    .or_else(|| Self::__suggest(#input_ident).and_then(Self::__from_label))
+            }
+        }
+    });
+    let trace_input = trace_fallback(&ident, &input_ident);
+    let trace_v = trace_fallback(&ident, &Ident::new("v", call_site));
+    let from_str = match (other, &input.attrs.default, input.attrs.error) {
+        (Some(other), ..) => {
+            if other_is_owned {
+                let other = &other.ident;
+                quote! {
+                    impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+                        type Err = ::core::convert::Infallible;
+                        #inline
+                        fn from_str(#input_ident: &str) -> ::core::result::Result<Self, ::core::convert::Infallible> {
+                            ::core::result::Result::Ok(match Self::__from_label(#input_ident) {
+                                ::core::option::Option::Some(value) => value,
+                                ::core::option::Option::None => {
+                                    #trace_input
+                                    Self::#other(::core::convert::From::from(<#string_type as ::core::convert::From<&str>>::from(#input_ident)))
+                                }
+                            })
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            }
+        }
+        (None, Some(default), _) => {
+            let default = constructor_of(&input.variants, default);
+            quote! {
+            impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+                type Err = ::core::convert::Infallible;
+                #inline
+                fn from_str(#input_ident: &str) -> ::core::result::Result<Self, ::core::convert::Infallible> {
+                    ::core::result::Result::Ok(match Self::__from_label(#input_ident) {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => {
+                            #trace_input
+                            Self::#default
+                        }
+                    })
+                }
+            }
+            }
+        }
+        (None, None, ErrorType::Unit) => quote! {
+            impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+                type Err = ();
+                #inline
+                fn from_str(#input_ident: &str) -> ::core::result::Result<Self, ()> {
+                    Self::__from_label(#input_ident).ok_or(())
+                }
+            }
+        },
+        (None, None, ErrorType::Struct) => {
+            let error_ident = Ident::new(&format(format_args!("{ident}ParseError")), call_site);
+            let error_def =
+                labeled_parse_error(&input.vis, &ident, &error_ident, suggest_fn.as_ref());
+            let error = if has_alloc {
+                quote! {
+                    #error_ident {
+                        input: ::core::convert::From::from(#input_ident),
+                    }
+                }
+            } else {
+                quote! {
+                    #error_ident::new(#input_ident)
+                }
+            };
+            // Keep constructing the error out of line of the successful path.
+            let error_fn = input.attrs.inline.then(|| {
+                quote! {
+                    #[cold]
+                    #[inline(never)]
+                    fn parse_error(#input_ident: &str) -> #error_ident {
+                        #error
+                    }
+                }
+            });
+            let error = if input.attrs.inline {
+                quote! { parse_error(#input_ident) }
+            } else {
+                error
+            };
+            quote! {
+                #error_def
+
+                impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+                    type Err = #error_ident;
+                    #inline
+                    fn from_str(#input_ident: &str) -> ::core::result::Result<Self, #error_ident> {
+                        #error_fn
+                        match Self::__from_label(#input_ident) {
+                            ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                            ::core::option::Option::None => ::core::result::Result::Err(#error),
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    // How `from_bytes` fails on invalid UTF-8, matching the way `from_str` fails on unknown input.
+    let invalid_utf8 = match (other, &input.attrs.default, input.attrs.error) {
+        (Some(_), ..) => other_is_owned.then(|| {
+            let cow_type = cow_type();
+            quote! {
+                ::core::result::Result::Err(_) => <Self as ::core::str::FromStr>::from_str(
+                    &#cow_type::into_owned(#string_type::from_utf8_lossy(bytes)),
+                )
+            }
+        }),
+        (None, Some(default), _) => {
+            let default = constructor_of(&input.variants, default);
+            Some(quote! {
+                ::core::result::Result::Err(_) => ::core::result::Result::Ok(Self::#default)
+            })
+        }
+        (None, None, ErrorType::Unit) => Some(quote! {
+            ::core::result::Result::Err(_) => ::core::result::Result::Err(())
+        }),
+        (None, None, ErrorType::Struct) => {
+            let error_ident = Ident::new(&format(format_args!("{ident}ParseError")), call_site);
+            Some(if has_alloc {
+                let cow_type = cow_type();
+                quote! {
+                    ::core::result::Result::Err(_) => ::core::result::Result::Err(#error_ident {
+                        input: #cow_type::into_owned(#string_type::from_utf8_lossy(bytes)),
+                    })
+                }
+            } else {
+                quote! {
+                    ::core::result::Result::Err(error) => {
+                        let valid = ::core::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap_or_default();
+                        ::core::result::Result::Err(#error_ident {
+                            input_len: bytes.len(),
+                            ..#error_ident::new(valid)
+                        })
+                    }
+                }
+            })
+        }
+    };
+    let from_bytes = invalid_utf8.map(|invalid_utf8| {
+        quote! {
+            /// Parses a label from bytes, failing like `from_str` when they are not valid UTF-8.
+            pub fn from_bytes(bytes: &[u8]) -> ::core::result::Result<Self, <Self as ::core::str::FromStr>::Err> {
+                match ::core::str::from_utf8(bytes) {
+                    ::core::result::Result::Ok(s) => <Self as ::core::str::FromStr>::from_str(s),
+                    #invalid_utf8,
+                }
+            }
+        }
+    });
+
+    // Parsing only fails when unknown strings have no variant to fall back to.
+    let (parse_str, borrowed_error) = (other.is_none() && input.attrs.default.is_none())
+        .then(|| {
+            let vis = &input.vis;
+            let error_ident = format_ident!("{}BorrowedParseError", ident);
+            let doc = format(format_args!(
+                "Error returned by [`{ident}::parse_str`], borrowing the string that does not \
+                 match any label."
+            ));
+            let error_impl = cfg!(feature = "std").then(|| {
+                quote! {
+                    impl ::std::error::Error for #error_ident<'_> {}
+                }
+            });
+            let parse_str = quote! {
+                /// Parses a label like `from_str`, failing with an error that borrows the input
+                /// instead of copying it, so that no allocation takes place.
+                pub fn parse_str(#input_ident: &str) -> ::core::result::Result<Self, #error_ident<'_>> {
+                    match Self::__from_label(#input_ident) {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                        ::core::option::Option::None => ::core::result::Result::Err(#error_ident { input: #input_ident }),
+                    }
+                }
+            };
+            let borrowed_error = quote! {
+                #[doc = #doc]
+                #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::marker::Copy, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+                #vis struct #error_ident<'a> {
+                    /// The input that failed to parse.
+                    pub input: &'a str,
+                }
+
+                impl ::core::fmt::Display for #error_ident<'_> {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.write_fmt(::core::format_args!("invalid {}: {}", ::core::stringify!(#ident), self.input))
+                    }
+                }
+
+                #error_impl
+            };
+            (parse_str, borrowed_error)
+        })
+        .unzip();
+
+    let visit_str_fallback = match (other, &input.attrs.default) {
+        (Some(other), _) => {
+            if other_is_owned {
+                let other = &other.ident;
+                quote! {
+                    {
+                        #trace_v
+                        ::core::result::Result::Ok(#ident::#other(::core::convert::From::from(<#string_type as ::core::convert::From<&str>>::from(v))))
+                    }
+                }
+            } else {
+                quote! { ::core::result::Result::Err(E::invalid_type(::serde::de::Unexpected::Str(v), &self)) }
+            }
+        }
+        (None, Some(default)) => {
+            let default = constructor_of(&input.variants, default);
+            quote! {
+                {
+                    #trace_v
+                    ::core::result::Result::Ok(#ident::#default)
+                }
+            }
+        }
+        (None, None) if suggestions => quote! {
+            match Self::Value::__suggest(v) {
+                ::core::option::Option::Some(suggestion) => ::core::result::Result::Err(E::custom(::core::format_args!("unknown {} {:?}, did you mean {:?}?", ::core::stringify!(#ident), v, suggestion))),
+                ::core::option::Option::None => ::core::result::Result::Err(E::invalid_value(::serde::de::Unexpected::Str(v), &self)),
+            }
+        },
+        (None, None) => {
+            quote! { ::core::result::Result::Err(E::invalid_value(::serde::de::Unexpected::Str(v), &self)) }
+        }
+    };
+    // Seeds and parsers choose how strings are matched at runtime, sharing a hidden function that
+    // takes the options. Both only apply to enums without generics, so their types need none
+    // either.
+    let case_insensitive = cfg!(feature = "unicase");
+    let from_label_with = (input.attrs.seed.is_some() || input.attrs.parser.is_some()).then(|| {
+            let eq_ignore_case = if cfg!(feature = "unicase") {
+                eq_folded(&input_ident, &quote! { label }, input.attrs.case_fold)
+            } else {
+                quote! { #input_ident.eq_ignore_ascii_case(label) }
+            };
+            let match_variants = input
+                .variants
+                .iter()
+                .filter(|variant| !variant.attrs.holds_string())
+                .map(|variant| {
+                    let constructor = variant.constructor();
+                    let string = variant.attrs.string.as_ref().unwrap();
+                    let mut labels = vec![string.clone()];
+                    labels.extend(
+                        input
+                            .attrs
+                            .serialize_case
+                            .zip(string.as_lit())
+                            .map(|(case, string)| Label::Lit(case.apply(string))),
+                    );
+                    let mut aliases = variant.attrs.aliases.clone();
+                    if input.attrs.percent_decode {
+                        for labels in [&mut labels, &mut aliases] {
+                            let encodings = labels
+                                .iter()
+                                .filter_map(Label::as_lit)
+                                .flat_map(|label| percent::encodings(&label.value()))
+                                .collect::<Vec<_>>();
+                            labels.extend(
+                                encodings
+                                    .iter()
+                                    .map(|encoding| Label::Lit(LitStr::new(encoding, call_site))),
+                            );
+                        }
+                    }
+                    let match_aliases = (!aliases.is_empty()).then(|| {
+                        quote! {
+                            if allow_aliases {
+                                #(
+                                    if matches(#input_ident, #aliases) {
+                                        return ::core::option::Option::Some(#ident::#constructor);
+                                    }
+                                )*
+                            }
+                        }
+                    });
+                    quote! {
+                        #(
+                            if matches(#input_ident, #labels) {
+                                return ::core::option::Option::Some(#ident::#constructor);
+                            }
+                        )*
+                        #match_aliases
+                    }
+                });
+        quote! {
+            fn __from_label_with(#input_ident: &str, case_insensitive: bool, allow_aliases: bool) -> ::core::option::Option<Self> {
+                let matches = |#input_ident: &str, label: &str| {
+                    if case_insensitive {
+                        #eq_ignore_case
+                    } else {
+                        #input_ident == label
+                    }
+                };
+                #strip_qualifier
+                #(#match_variants)*
+                ::core::option::Option::None
+            }
+        }
+    });
+    let seed = if input.attrs.seed.is_some() {
+        let seed_deserialize = if input.attrs.self_describing {
+            quote! { deserializer.deserialize_any(self) }
+        } else {
+            quote! { deserializer.deserialize_str(self) }
+        };
+        let vis = &input.vis;
+        let seed_ident = Ident::new(&format(format_args!("{ident}Seed")), call_site);
+        let doc = format(format_args!(
+            "Deserializes [`{ident}`] from a string, with matching options chosen at runtime."
+        ));
+        quote! {
+            #[doc = #doc]
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::marker::Copy, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            #vis struct #seed_ident {
+                /// Whether labels are matched regardless of case.
+                pub case_insensitive: bool,
+                /// Whether aliases are matched in addition to labels.
+                pub allow_aliases: bool,
+            }
+
+            impl ::core::default::Default for #seed_ident {
+                /// Returns the options used by the `Deserialize` implementation of the enum.
+                fn default() -> Self {
+                    Self {
+                        case_insensitive: #case_insensitive,
+                        allow_aliases: true,
+                    }
+                }
+            }
+
+            impl #seed_ident {
+                /// Returns the variant matching the given string under these options, if any.
+                pub fn from_label(&self, #input_ident: &str) -> ::core::option::Option<#ident> {
+                    #ident::__from_label_with(#input_ident, self.case_insensitive, self.allow_aliases)
+                }
+            }
+
+            impl<'de> ::serde::de::DeserializeSeed<'de> for #seed_ident {
+                type Value = #ident;
+
+                fn deserialize<D>(self, deserializer: D) -> ::core::result::Result<Self::Value, D::Error> where D: ::serde::Deserializer<'de> {
+                    #seed_deserialize
+                }
+            }
+
+            impl<'de> ::serde::de::Visitor<'de> for #seed_ident {
+                type Value = #ident;
+
+                fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    formatter.write_fmt(::core::format_args!("a valid {} string value", ::core::stringify!(#ident)))
+                }
+
+                fn visit_str<E>(self, v: &str) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                    match self.from_label(v) {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                        ::core::option::Option::None => #visit_str_fallback,
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let parser = input.attrs.parser.map(|_| {
+        let vis = &input.vis;
+        let parser_ident = format_ident!("{}Parser", ident);
+        let doc = format(format_args!(
+            "Parses [`{ident}`] from strings, with matching options chosen at runtime."
+        ));
+        // Strings matching no label fail or fall back the same way as in `from_str`.
+        let fallback = match (other, &input.attrs.default, input.attrs.error) {
+            (Some(other), ..) => {
+                let other = &other.ident;
+                quote! {
+                    ::core::result::Result::Ok(#ident::#other(::core::convert::From::from(<#string_type as ::core::convert::From<&str>>::from(#input_ident))))
+                }
+            }
+            (None, Some(default), _) => {
+                let default = constructor_of(&input.variants, default);
+                quote! { ::core::result::Result::Ok(#ident::#default) }
+            }
+            (None, None, ErrorType::Unit) => quote! { ::core::result::Result::Err(()) },
+            (None, None, ErrorType::Struct) => {
+                let error_ident = format_ident!("{}ParseError", ident);
+                if has_alloc {
+                    quote! {
+                        ::core::result::Result::Err(#error_ident {
+                            input: ::core::convert::From::from(#input_ident),
+                        })
+                    }
+                } else {
+                    quote! { ::core::result::Result::Err(#error_ident::new(#input_ident)) }
+                }
+            }
+        };
+        quote! {
+            #[doc = #doc]
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::marker::Copy, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            #vis struct #parser_ident {
+                case_insensitive: bool,
+                aliases: bool,
+            }
+
+            impl ::core::default::Default for #parser_ident {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl #parser_ident {
+                /// Returns a parser with the options used by the `FromStr` implementation of the
+                /// enum.
+                pub const fn new() -> Self {
+                    Self {
+                        case_insensitive: #case_insensitive,
+                        aliases: true,
+                    }
+                }
+
+                /// Sets whether labels are matched regardless of case.
+                pub const fn case_insensitive(self, case_insensitive: bool) -> Self {
+                    Self { case_insensitive, ..self }
+                }
+
+                /// Sets whether aliases are matched in addition to labels.
+                pub const fn aliases(self, aliases: bool) -> Self {
+                    Self { aliases, ..self }
+                }
+
+                /// Parses the string under these options, failing or falling back like `from_str`
+                /// when it matches no label.
+                pub fn parse(&self, #input_ident: &str) -> ::core::result::Result<#ident, <#ident as ::core::str::FromStr>::Err> {
+                    match #ident::__from_label_with(#input_ident, self.case_insensitive, self.aliases) {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                        ::core::option::Option::None => #fallback,
+                    }
+                }
+            }
+        }
+    });
+
+    let visit_borrowed_str = match other {
+        Some(other) => {
+            let other = &other.ident;
+            quote! {
+                fn visit_borrowed_str<E>(self, v: &'de str) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                    ::core::result::Result::Ok(match Self::Value::__from_label(v) {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => {
+                            #trace_v
+                            #ident::#other(::core::convert::From::from(v))
+                        }
+                    })
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    let visit_null = match &input.attrs.null {
+        Some(null) => {
+            let null = constructor_of(&input.variants, null);
+            quote! {
+            fn visit_none<E>(self) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                ::core::result::Result::Ok(#ident::#null)
+            }
+
+            fn visit_unit<E>(self) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                ::core::result::Result::Ok(#ident::#null)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> ::core::result::Result<Self::Value, D::Error> where D: ::serde::Deserializer<'de> {
+                deserializer.deserialize_str(self)
+            }
+            }
+        }
+        None => quote! {},
+    };
+
+    let bool_variants = input
+        .variants
+        .iter()
+        .filter_map(|variant| Some((variant.attrs.bool.as_ref()?.value, variant.constructor())))
+        .collect::<Vec<_>>();
+    let visit_bool = if bool_variants.is_empty() {
+        quote! {}
+    } else {
+        let bool_result = |value: bool| match bool_variants.iter().find(|(bool, _)| *bool == value)
+        {
+            Some((_, constructor)) => {
+                quote! { ::core::result::Result::Ok(#ident::#constructor) }
+            }
+            None => {
+                quote! { ::core::result::Result::Err(E::invalid_value(::serde::de::Unexpected::Bool(v), &self)) }
+            }
+        };
+        let true_result = bool_result(true);
+        let false_result = bool_result(false);
+        quote! {
+            fn visit_bool<E>(self, v: bool) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                if v {
+                    #true_result
+                } else {
+                    #false_result
+                }
+            }
+        }
+    };
+
+    let number_variants = input
+        .variants
+        .iter()
+        .filter_map(|variant| Some((variant.attrs.number?, variant.constructor())))
+        .collect::<Vec<_>>();
+    let visit_number = if number_variants.is_empty() {
+        quote! {}
+    } else {
+        let unsigned_match = number_variants.iter().map(|(number, constructor)| {
+            let number = Literal::u64_unsuffixed(*number);
+            quote! { #number => ::core::result::Result::Ok(#ident::#constructor), }
+        });
+        let signed_match = number_variants.iter().filter_map(|(number, constructor)| {
+            let number = Literal::i64_unsuffixed(i64::try_from(*number).ok()?);
+            Some(quote! { #number => ::core::result::Result::Ok(#ident::#constructor), })
+        });
+        quote! {
+            fn visit_u64<E>(self, v: u64) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                match v {
+                    #(#unsigned_match)*
+                    _ => ::core::result::Result::Err(E::invalid_value(::serde::de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_i64<E>(self, v: i64) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                match v {
+                    #(#signed_match)*
+                    _ => ::core::result::Result::Err(E::invalid_value(::serde::de::Unexpected::Signed(v), &self)),
+                }
+            }
+        }
+    };
+
+    // Enums parsed from their labels alone share the visitor in the runtime crate.
+    let shared_visitor = cfg!(feature = "runtime")
+        && !input.attrs.public_visitor
+        && !input.char_labels
+        && other.is_none()
+        && input.attrs.default.is_none()
+        && input.attrs.null.is_none()
+        && !input.attrs.self_describing
+        && bool_variants.is_empty()
+        && number_variants.is_empty();
+    let visitor_def = if input.attrs.public_visitor {
+        let vis = &input.vis;
+        let doc = format(format_args!(
+            "Visitor deserializing [`{ident}`] like its `Deserialize` implementation, for use in \
+             other `Deserialize` implementations."
+        ));
+        quote! {
+            #[doc = #doc]
+            #vis struct #visitor_ident #impl_generics #where_clause {
+                marker: ::core::marker::PhantomData<#ident #ty_generics>,
+            }
+
+            impl #impl_generics #visitor_ident #ty_generics #where_clause {
+                /// Creates a new visitor.
+                pub const fn new() -> Self {
+                    Self {
+                        marker: ::core::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl #impl_generics ::core::default::Default for #visitor_ident #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+        }
+    } else {
+        quote! {
+            struct #visitor_ident #impl_generics #where_clause {
+                marker: ::core::marker::PhantomData<#ident #ty_generics>,
+            }
+        }
+    };
+    let suggest_label = suggestions.then(|| {
+        quote! {
+            fn suggest(#input_ident: &str) -> ::core::option::Option<&'static str> {
+                Self::__suggest(#input_ident)
+            }
+        }
+    });
+    // The map is held in a static, which cannot name the generic parameters of the enum, and must
+    // be shareable across threads, which the fields of a variant might not be.
+    let label_map = (cfg!(feature = "std")
+        && input.generics.params.is_empty()
+        && input
+            .variants
+            .iter()
+            .all(|variant| matches!(variant.fields, Fields::Unit)))
+    .then(|| {
+        let ident = &ident;
+        let inserts = spellings.iter().flat_map(|(variant, spellings)| {
+            let variant_ident = &variant.ident;
+            spellings.iter().map(move |spelling| {
+                quote! {
+                    map.entry(#spelling).or_insert(#ident::#variant_ident);
+                }
+            })
+        });
+        quote! {
+            /// Returns a map from each label and alias to its variant, built on first use.
+            ///
+            /// Keys are the exact spellings, so lookups in the map are always case-sensitive.
+            pub fn label_map() -> &'static ::std::collections::HashMap<&'static str, Self> {
+                static LABEL_MAP: ::std::sync::LazyLock<::std::collections::HashMap<&'static str, #ident>> =
+                    ::std::sync::LazyLock::new(|| {
+                        let mut map = ::std::collections::HashMap::new();
+                        #(#inserts)*
+                        map
+                    });
+                &LABEL_MAP
+            }
+        }
+    });
+    let tests = input
+        .attrs
+        .generate_tests
+        .map(|_| generate_tests(&ident, &input.generics, &input.variants, &input.attrs));
+    let meta = cfg!(feature = "runtime").then(|| {
+        let case_sensitive = !cfg!(feature = "unicase");
+        let variants = input.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            let label = match &variant.attrs.string {
+                Some(string) => quote! { ::core::option::Option::Some(#string) },
+                None => quote! { ::core::option::Option::None },
+            };
+            let aliases = &variant.attrs.aliases;
+            quote! {
+                ::serde_string_enum_runtime::VariantMeta {
+                    name: ::core::stringify!(#variant_ident),
+                    label: #label,
+                    aliases: &[#(#aliases),*],
+                }
+            }
+        });
+        quote! {
+            /// A description of the enum and the strings each variant is parsed from.
+            pub const META: ::serde_string_enum_runtime::EnumMeta = ::serde_string_enum_runtime::EnumMeta {
+                name: ::core::stringify!(#ident),
+                variants: &[#(#variants),*],
+                case_sensitive: #case_sensitive,
+            };
+        }
+    });
+    // Variants are listed in a constant, which cannot name the generic parameters of the enum or
+    // construct default field values.
+    let string_enum = if cfg!(feature = "runtime")
+        && input.generics.params.is_empty()
+        && input
+            .variants
+            .iter()
+            .all(|variant| !variant.has_default_fields())
+    {
+        let match_labels = input.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            if variant.attrs.holds_string() {
+                return quote! { Self::#variant_ident(value) => value, };
+            }
+            let string = variant.attrs.string.as_ref().unwrap();
+            quote! { Self::#variant_ident => #string, }
+        });
+        let match_aliases = input.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            if variant.attrs.holds_string() {
+                return quote! { Self::#variant_ident(_) => &[], };
+            }
+            let aliases = &variant.attrs.aliases;
+            quote! { Self::#variant_ident => &[#(#aliases),*], }
+        });
+        let variant_idents = input
+            .variants
+            .iter()
+            .filter(|variant| !variant.attrs.holds_string())
+            .map(|variant| &variant.ident);
+        quote! {
+            impl ::serde_string_enum_runtime::StringEnum for #ident {
+                fn label(&self) -> &str {
+                    match self {
+                        #(#match_labels)*
+                    }
+                }
+
+                fn aliases(&self) -> &'static [&'static str] {
+                    match self {
+                        #(#match_aliases)*
+                    }
+                }
+
+                fn from_label(#input_ident: &str) -> ::core::option::Option<Self> {
+                    Self::__from_label(#input_ident)
+                }
+
+                fn variants() -> &'static [Self] {
+                    const VARIANTS: &[#ident] = &[#(#ident::#variant_idents),*];
+                    VARIANTS
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let visitor_impl = if shared_visitor {
+        quote! {
+            impl #impl_generics ::serde_string_enum_runtime::label::FromLabel for #ident #ty_generics #where_clause {
+                const NAME: &'static str = ::core::stringify!(#ident);
+
+                fn from_label(#input_ident: &str) -> ::core::option::Option<Self> {
+                    Self::__from_label(#input_ident)
+                }
+
+                #suggest_label
+            }
+        }
+    } else {
+        quote! {
+            #visitor_def
+
+            impl #de_impl_generics ::serde::de::Visitor<'de> for #visitor_ident #ty_generics #where_clause {
+                type Value = #ident #ty_generics;
+
+                fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    formatter.write_fmt(::core::format_args!("a valid {} string value", ::core::stringify!(#ident)))
+                }
+
+                #inline
+                fn visit_str<E>(self, v: &str) -> ::core::result::Result<Self::Value, E> where E: ::serde::de::Error {
+                    match Self::Value::__from_label(v) {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                        ::core::option::Option::None => #visit_str_fallback,
+                    }
+                }
+
+                #visit_borrowed_str
+
+                #visit_null
+
+                #visit_bool
+
+                #visit_number
+            }
+        }
+    };
+
+    // Values other than strings can only be distinguished by self-describing formats.
+    let visitor = quote! { #visitor_ident { marker: ::core::marker::PhantomData } };
+    let deserialize = if shared_visitor {
+        quote! { ::serde_string_enum_runtime::label::deserialize(deserializer) }
+    } else if input.attrs.self_describing
+        || !bool_variants.is_empty()
+        || !number_variants.is_empty()
+    {
+        quote! { deserializer.deserialize_any(#visitor) }
+    } else if input.attrs.null.is_some() {
+        quote! { deserializer.deserialize_option(#visitor) }
+    } else if input.char_labels {
+        quote! { deserializer.deserialize_char(#visitor) }
+    } else {
+        quote! { deserializer.deserialize_str(#visitor) }
+    };
+    let deserialize_impl = match (&input.attrs.serde_as, &input.attrs.with_module) {
+        (Some(adapter), _) => quote! {
+            impl #de_impl_generics ::serde_with::DeserializeAs<'de, #ident #ty_generics> for #adapter #where_clause {
+                fn deserialize_as<D>(deserializer: D) -> ::core::result::Result<#ident #ty_generics, D::Error> where D: ::serde::Deserializer<'de> {
+                    #deserialize
+                }
+            }
+        },
+        (None, Some(module)) => {
+            let with_module = with_module_def(&input.vis, &ident, module);
+            quote! {
+                impl #ident {
+                    fn __deserialize<'de, D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: ::serde::Deserializer<'de> {
+                        #deserialize
+                    }
+                }
+
+                #with_module
+            }
+        }
+        (None, None) => quote! {
+            impl #de_impl_generics ::serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+                fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: ::serde::Deserializer<'de> {
+                    #deserialize
+                }
+            }
+        },
+    };
+    // Conversions through serde go through the adapter or the `with_module` functions when the
+    // enum does not implement `Deserialize` itself.
+    let deserialize_from = |deserializer: proc_macro2::TokenStream| match (
+        &input.attrs.serde_as,
+        &input.attrs.with_module,
+    ) {
+        (Some(adapter), _) => quote! {
+            <#adapter as ::serde_with::DeserializeAs<'_, Self>>::deserialize_as(#deserializer)
+        },
+        (None, Some(_)) => quote! { Self::__deserialize(#deserializer) },
+        (None, None) => quote! { ::serde::Deserialize::deserialize(#deserializer) },
+    };
+
+    let json = if input.attrs.json {
+        let deserialize = deserialize_from(quote! { value });
+        quote! {
+            impl #de_impl_generics ::core::convert::TryFrom<&'de ::serde_json::Value> for #ident #ty_generics #where_clause {
+                type Error = ::serde_json::Error;
+                fn try_from(value: &'de ::serde_json::Value) -> ::core::result::Result<Self, ::serde_json::Error> {
+                    #deserialize
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Conversions from foreign strings go through `FromStr`, which is not implemented when the
+    // "other" variant borrows from the input.
+    let has_from_str = other.is_none() || other_is_owned;
+
+    let pyo3 = if input.attrs.pyo3 && has_from_str {
+        let mut py_generics = input.generics.clone();
+        py_generics.params.insert(0, parse_quote! { 'py });
+        py_generics.params.insert(0, parse_quote! { '__a });
+        let (py_impl_generics, _, _) = py_generics.split_for_impl();
+        quote! {
+            impl #py_impl_generics ::pyo3::FromPyObject<'__a, 'py> for #ident #ty_generics #where_clause {
+                type Error = ::pyo3::PyErr;
+
+                fn extract(ob: ::pyo3::Borrowed<'__a, 'py, ::pyo3::PyAny>) -> ::pyo3::PyResult<Self> {
+                    let s: ::std::string::String = ::pyo3::types::PyAnyMethods::extract(&*ob)?;
+                    <Self as ::core::str::FromStr>::from_str(&s).map_err(|_| {
+                        ::pyo3::exceptions::PyValueError::new_err(::std::format!("invalid {}: {}", ::core::stringify!(#ident), s))
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let wasm_bindgen = if input.attrs.wasm_bindgen && has_from_str {
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<::wasm_bindgen::JsValue> for #ident #ty_generics #where_clause {
+                type Error = ::wasm_bindgen::JsValue;
+                fn try_from(value: ::wasm_bindgen::JsValue) -> ::core::result::Result<Self, ::wasm_bindgen::JsValue> {
+                    match value.as_string() {
+                        ::core::option::Option::Some(s) => <Self as ::core::str::FromStr>::from_str(&s).map_err(|_| value),
+                        ::core::option::Option::None => ::core::result::Result::Err(value),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // BSON documents are decoded from owned values, so the enum cannot borrow from them.
+    let bson = if input.attrs.bson && has_from_str && input.generics.lifetimes().next().is_none() {
+        let deserialize = if input.attrs.serde_as.is_some() || input.attrs.with_module.is_some() {
+            deserialize_from(quote! { ::bson::Deserializer::new(value) })
+        } else {
+            quote! { ::bson::from_bson(value) }
+        };
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<::bson::Bson> for #ident #ty_generics #where_clause {
+                type Error = ::bson::de::Error;
+                fn try_from(value: ::bson::Bson) -> ::core::result::Result<Self, ::bson::de::Error> {
+                    #deserialize
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let redis = if input.attrs.redis && has_from_str {
+        quote! {
+            impl #impl_generics ::redis::FromRedisValue for #ident #ty_generics #where_clause {
+                fn from_redis_value(v: &::redis::Value) -> ::redis::RedisResult<Self> {
+                    let s: ::std::string::String = ::redis::FromRedisValue::from_redis_value(v)?;
+                    <Self as ::core::str::FromStr>::from_str(&s).map_err(|_| {
+                        ::core::convert::From::from((
+                            ::redis::ErrorKind::TypeError,
+                            ::core::concat!("invalid ", ::core::stringify!(#ident)),
+                            s,
+                        ))
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let minicbor = if input.attrs.minicbor && has_from_str {
+        let mut decode_generics = input.generics.clone();
+        decode_generics.params.insert(0, parse_quote! { '__b });
+        decode_generics.params.push(parse_quote! { __C });
+        let (decode_impl_generics, _, _) = decode_generics.split_for_impl();
+        quote! {
+            impl #decode_impl_generics ::minicbor::Decode<'__b, __C> for #ident #ty_generics #where_clause {
+                fn decode(d: &mut ::minicbor::Decoder<'__b>, _: &mut __C) -> ::core::result::Result<Self, ::minicbor::decode::Error> {
+                    <Self as ::core::str::FromStr>::from_str(d.str()?).map_err(|_| {
+                        ::minicbor::decode::Error::message(::core::concat!("invalid ", ::core::stringify!(#ident)))
+                    })
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let zbus = if input.attrs.zbus && has_from_str {
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<::zvariant::Value<'_>> for #ident #ty_generics #where_clause {
+                type Error = ::zvariant::Error;
+
+                fn try_from(value: ::zvariant::Value<'_>) -> ::core::result::Result<Self, ::zvariant::Error> {
+                    let s = <&str as ::core::convert::TryFrom<&::zvariant::Value<'_>>>::try_from(&value)?;
+                    <Self as ::core::str::FromStr>::from_str(s).map_err(|_| {
+                        ::zvariant::Error::Message(::std::format!("invalid {}: {}", ::core::stringify!(#ident), s))
+                    })
+                }
+            }
+
+            impl #impl_generics ::core::convert::TryFrom<::zvariant::OwnedValue> for #ident #ty_generics #where_clause {
+                type Error = ::zvariant::Error;
+
+                fn try_from(value: ::zvariant::OwnedValue) -> ::core::result::Result<Self, ::zvariant::Error> {
+                    <Self as ::core::convert::TryFrom<::zvariant::Value<'_>>>::try_from(::core::convert::From::from(value))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Custom types convert both ways in one trait, so labels are written here rather than through
+    // the serializing derive.
+    let uniffi = if input.attrs.uniffi.is_some() {
+        let patterns = input.variants.iter().map(|variant| variant.pattern());
+        let labels = input
+            .variants
+            .iter()
+            .map(|variant| variant.attrs.string.as_ref().unwrap());
+        quote! {
+            impl crate::UniffiCustomTypeConverter for #ident {
+                type Builtin = ::std::string::String;
+
+                fn into_custom(val: Self::Builtin) -> ::uniffi::Result<Self> {
+                    <Self as ::core::str::FromStr>::from_str(&val).map_err(|_| {
+                        ::uniffi::deps::anyhow::Error::msg(::std::format!("invalid {}: {}", ::core::stringify!(#ident), val))
+                    })
+                }
+
+                fn from_custom(obj: Self) -> Self::Builtin {
+                    match obj {
+                        #(Self::#patterns => ::core::convert::From::from(#labels),)*
+                    }
+                }
+            }
+
+            ::uniffi::custom_type!(#ident, ::std::string::String);
+        }
+    } else {
+        quote! {}
+    };
+
+    let rkyv = if input.attrs.rkyv {
+        let mut de_generics = input.generics.clone();
+        de_generics
+            .params
+            .push(parse_quote! { __D: ::rkyv::rancor::Fallible + ?::core::marker::Sized });
+        de_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { __D::Error: ::rkyv::rancor::Source });
+        let (rkyv_impl_generics, _, rkyv_where_clause) = de_generics.split_for_impl();
+        let (archived, from_archived) = match input.attrs.archive_as {
+            ArchiveAs::Label => (
+                quote! { ::rkyv::string::ArchivedString },
+                quote! { #ident::__from_label(self.as_str()) },
+            ),
+            ArchiveAs::Index => {
+                let constructors = input.variants.iter().map(|variant| variant.constructor());
+                let indices = (0..input.variants.len() as u32).map(Literal::u32_unsuffixed);
+                (
+                    quote! { ::rkyv::primitive::ArchivedU32 },
+                    quote! {
+                        match self.to_native() {
+                            #(#indices => ::core::option::Option::Some(#ident::#constructors),)*
+                            _ => ::core::option::Option::None,
+                        }
+                    },
+                )
+            }
+        };
+        let message = format(format_args!("unknown archived {ident}"));
+        quote! {
+            impl #rkyv_impl_generics ::rkyv::Deserialize<#ident #ty_generics, __D> for #archived #rkyv_where_clause {
+                fn deserialize(&self, _: &mut __D) -> ::core::result::Result<#ident #ty_generics, __D::Error> {
+                    #[derive(::core::fmt::Debug)]
+                    struct UnknownVariant;
+
+                    impl ::core::fmt::Display for UnknownVariant {
+                        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                            f.write_str(#message)
+                        }
+                    }
+
+                    impl ::core::error::Error for UnknownVariant {}
+
+                    match #from_archived {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                        ::core::option::Option::None => ::core::result::Result::Err(<__D::Error as ::rkyv::rancor::Source>::new(UnknownVariant)),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let sea_orm = if input.attrs.sea_orm {
+        let constructors = input.variants.iter().map(|variant| variant.constructor());
+        let patterns = input.variants.iter().map(|variant| variant.pattern());
+        let variant_count = input.variants.len();
+        let indices = 0..variant_count;
+        let labels = input
+            .variants
+            .iter()
+            .map(|variant| variant.attrs.string.as_ref().unwrap());
+        let active_enum = quote! { <#ident #ty_generics as ::sea_orm::ActiveEnum> };
+        let value = quote! { <#ident #ty_generics as ::sea_orm::ActiveEnum>::Value };
+        quote! {
+            impl #impl_generics ::sea_orm::Iterable for #ident #ty_generics #where_clause {
+                // Variants are built from their indices, so the iterator is `Clone` even when the
+                // enum is not.
+                type Iterator = ::core::iter::Map<::core::ops::Range<usize>, fn(usize) -> Self>;
+                fn iter() -> Self::Iterator {
+                    ::core::iter::Iterator::map(0..#variant_count, |index| match index {
+                        #(#indices => Self::#constructors,)*
+                        _ => ::core::unreachable!(),
+                    })
+                }
+            }
+
+            impl #impl_generics ::sea_orm::ActiveEnum for #ident #ty_generics #where_clause {
+                type Value = ::std::string::String;
+                type ValueVec = ::std::vec::Vec<::std::string::String>;
+
+                fn name() -> ::sea_orm::sea_query::DynIden {
+                    ::sea_orm::sea_query::SeaRc::new(::sea_orm::sea_query::Alias::new(::core::stringify!(#ident)))
+                }
+
+                fn to_value(&self) -> Self::Value {
+                    match self {
+                        #(Self::#patterns => ::core::convert::From::from(#labels),)*
+                    }
+                }
+
+                fn try_from_value(v: &Self::Value) -> ::core::result::Result<Self, ::sea_orm::DbErr> {
+                    <Self as ::core::str::FromStr>::from_str(v).map_err(|_| {
+                        ::sea_orm::DbErr::Type(::std::format!("invalid {}: {}", ::core::stringify!(#ident), v))
+                    })
+                }
+
+                fn db_type() -> ::sea_orm::ColumnDef {
+                    ::sea_orm::ColumnTypeTrait::def(::sea_orm::ColumnType::String(::sea_orm::sea_query::StringLen::None))
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::sea_orm::Value #where_clause {
+                fn from(source: #ident #ty_generics) -> Self {
+                    ::core::convert::From::from(#active_enum::to_value(&source))
+                }
+            }
+
+            impl #impl_generics ::sea_orm::TryGetable for #ident #ty_generics #where_clause {
+                fn try_get_by<I: ::sea_orm::ColIdx>(res: &::sea_orm::QueryResult, idx: I) -> ::core::result::Result<Self, ::sea_orm::TryGetError> {
+                    let value = <#value as ::sea_orm::TryGetable>::try_get_by(res, idx)?;
+                    #active_enum::try_from_value(&value).map_err(::sea_orm::TryGetError::DbErr)
+                }
+            }
+
+            impl #impl_generics ::sea_orm::sea_query::ValueType for #ident #ty_generics #where_clause {
+                fn try_from(v: ::sea_orm::Value) -> ::core::result::Result<Self, ::sea_orm::sea_query::ValueTypeErr> {
+                    let value = <#value as ::sea_orm::sea_query::ValueType>::try_from(v)?;
+                    #active_enum::try_from_value(&value).map_err(|_| ::sea_orm::sea_query::ValueTypeErr)
+                }
+
+                fn type_name() -> ::std::string::String {
+                    <#value as ::sea_orm::sea_query::ValueType>::type_name()
+                }
+
+                fn array_type() -> ::sea_orm::sea_query::ArrayType {
+                    <#value as ::sea_orm::sea_query::ValueType>::array_type()
+                }
+
+                fn column_type() -> ::sea_orm::sea_query::ColumnType {
+                    <#value as ::sea_orm::sea_query::ValueType>::column_type()
+                }
+            }
+
+            impl #impl_generics ::sea_orm::sea_query::Nullable for #ident #ty_generics #where_clause {
+                fn null() -> ::sea_orm::Value {
+                    <#value as ::sea_orm::sea_query::Nullable>::null()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let poem_openapi = if input.attrs.poem_openapi {
+        let patterns = input.variants.iter().map(|variant| variant.pattern());
+        let labels = input
+            .variants
+            .iter()
+            .map(|variant| variant.attrs.string.as_ref().unwrap())
+            .collect::<Vec<_>>();
+        quote! {
+            impl #impl_generics ::poem_openapi::types::Type for #ident #ty_generics #where_clause {
+                const IS_REQUIRED: bool = true;
+                type RawValueType = Self;
+                type RawElementValueType = Self;
+
+                fn name() -> ::std::borrow::Cow<'static, str> {
+                    ::std::borrow::Cow::Borrowed(::core::stringify!(#ident))
+                }
+
+                fn schema_ref() -> ::poem_openapi::registry::MetaSchemaRef {
+                    ::poem_openapi::registry::MetaSchemaRef::Reference(::core::convert::From::from(::core::stringify!(#ident)))
+                }
+
+                fn register(registry: &mut ::poem_openapi::registry::Registry) {
+                    registry.create_schema::<Self, _>(::core::convert::From::from(::core::stringify!(#ident)), |_| {
+                        let mut schema = ::poem_openapi::registry::MetaSchema::new("string");
+                        schema.enum_items = ::std::vec![#(::serde_json::Value::String(::core::convert::From::from(#labels))),*];
+                        schema
+                    });
+                }
+
+                fn as_raw_value(&self) -> ::core::option::Option<&Self::RawValueType> {
+                    ::core::option::Option::Some(self)
+                }
+
+                fn raw_element_iter<'a>(&'a self) -> ::std::boxed::Box<dyn ::core::iter::Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+                    ::std::boxed::Box::new(::core::iter::once(self))
+                }
+            }
+
+            impl #impl_generics ::poem_openapi::types::ParseFromJSON for #ident #ty_generics #where_clause {
+                fn parse_from_json(value: ::core::option::Option<::serde_json::Value>) -> ::poem_openapi::types::ParseResult<Self> {
+                    let value = value.unwrap_or_default();
+                    if let ::serde_json::Value::String(s) = &value {
+                        if let ::core::result::Result::Ok(value) = <Self as ::core::str::FromStr>::from_str(s) {
+                            return ::core::result::Result::Ok(value);
+                        }
+                    }
+                    ::core::result::Result::Err(::poem_openapi::types::ParseError::expected_type(value))
+                }
+            }
+
+            impl #impl_generics ::poem_openapi::types::ToJSON for #ident #ty_generics #where_clause {
+                fn to_json(&self) -> ::core::option::Option<::serde_json::Value> {
+                    let label = match self {
+                        #(Self::#patterns => #labels,)*
+                    };
+                    ::core::option::Option::Some(::serde_json::Value::String(::core::convert::From::from(label)))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let header = input.attrs.header_name.as_ref().map(|header_name| {
+        let patterns = input.variants.iter().map(|variant| variant.pattern());
+        let labels = input
+            .variants
+            .iter()
+            .map(|variant| variant.attrs.string.as_ref().unwrap());
+        quote! {
+            impl #impl_generics ::headers::Header for #ident #ty_generics #where_clause {
+                fn name() -> &'static ::headers::HeaderName {
+                    static NAME: ::headers::HeaderName = ::headers::HeaderName::from_static(#header_name);
+                    &NAME
+                }
+
+                fn decode<'i, I>(values: &mut I) -> ::core::result::Result<Self, ::headers::Error>
+                where
+                    I: ::core::iter::Iterator<Item = &'i ::headers::HeaderValue>,
+                {
+                    let value = values.next().ok_or_else(::headers::Error::invalid)?;
+                    let s = value.to_str().map_err(|_| ::headers::Error::invalid())?;
+                    <Self as ::core::str::FromStr>::from_str(s).map_err(|_| ::headers::Error::invalid())
+                }
+
+                fn encode<E>(&self, values: &mut E)
+                where
+                    E: ::core::iter::Extend<::headers::HeaderValue>,
+                {
+                    let label = match self {
+                        #(Self::#patterns => #labels,)*
+                    };
+                    values.extend(::core::iter::once(::headers::HeaderValue::from_static(label)));
+                }
+            }
+        }
+    });
+
+    let remote = input.attrs.remote.as_ref().map(|remote| {
+        let variant_idents = input
+            .variants
+            .iter()
+            .map(|variant| &variant.ident)
+            .collect::<Vec<_>>();
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Deserializes a value of the remote enum from the label of the variant of the
+                /// same name, for use with `#[serde(with = ...)]`.
+                pub fn deserialize<'de, D>(deserializer: D) -> ::core::result::Result<#remote, D::Error> where D: ::serde::Deserializer<'de> {
+                    ::core::result::Result::Ok(match <Self as ::serde::Deserialize<'de>>::deserialize(deserializer)? {
+                        #(Self::#variant_idents => #remote::#variant_idents,)*
+                    })
+                }
+            }
+        }
+    });
+
+    let prost = input.attrs.prost.as_ref().map(|proto| {
+        let variant_idents = input
+            .variants
+            .iter()
+            .map(|variant| &variant.ident)
+            .collect::<Vec<_>>();
+        quote! {
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for #proto #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    match value {
+                        #(#ident::#variant_idents => #proto::#variant_idents,)*
+                    }
+                }
+            }
+
+            impl #impl_generics ::core::convert::TryFrom<#proto> for #ident #ty_generics #where_clause {
+                type Error = #proto;
+
+                fn try_from(value: #proto) -> ::core::result::Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        #(#proto::#variant_idents => ::core::result::Result::Ok(Self::#variant_idents),)*
+                        _ => ::core::result::Result::Err(value),
+                    }
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for i32 #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    <#proto as ::core::convert::From<#ident #ty_generics>>::from(value) as i32
+                }
+            }
+
+            impl #impl_generics ::core::convert::TryFrom<i32> for #ident #ty_generics #where_clause {
+                type Error = i32;
+
+                fn try_from(value: i32) -> ::core::result::Result<Self, Self::Error> {
+                    match <#proto as ::core::convert::TryFrom<i32>>::try_from(value) {
+                        ::core::result::Result::Ok(proto) => <Self as ::core::convert::TryFrom<#proto>>::try_from(proto).map_err(|_| value),
+                        ::core::result::Result::Err(_) => ::core::result::Result::Err(value),
+                    }
+                }
+            }
+        }
+    });
+
+    let convert_from = input.attrs.convert_from.as_ref().map(|other| {
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<#other> for #ident #ty_generics #where_clause {
+                type Error = #other;
+
+                fn try_from(value: #other) -> ::core::result::Result<Self, Self::Error> {
+                    match Self::__from_label(::core::convert::AsRef::<str>::as_ref(&value)) {
+                        ::core::option::Option::Some(converted) => ::core::result::Result::Ok(converted),
+                        ::core::option::Option::None => ::core::result::Result::Err(value),
+                    }
+                }
+            }
+        }
+    });
+
+    debug_expand(
+        &input.attrs,
+        "DeserializeLabeledStringEnum",
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #from_label
+
+                #(#from_label_chunks)*
+
+                #from_label_with
+
+                #matches
+
+                #all_accepted_strings
+
+                #suggest
+
+                #completions
+
+                #from_bytes
+
+                #parse_str
+
+                #parse_prefix
+
+                #nom
+
+                #parse_list
+
+                #parse_detailed
+
+                #meta
+
+                #label_map
+            }
+
+            #from_str
+
+            #borrowed_error
+
+            #match_kind
+
+            #visitor_impl
+
+            #string_enum
+
+            #tests
+
+            #seed
+
+            #parser
+
+            #deserialize_impl
+
+            #json
+
+            #pyo3
+
+            #wasm_bindgen
+
+            #sea_orm
+
+            #poem_openapi
+
+            #header
+
+            #remote
+
+            #convert_from
+
+            #prost
+
+            #bson
+
+            #redis
+
+            #minicbor
+
+            #zbus
+
+            #uniffi
+
+            #c_ffi
+
+            #rkyv
+        },
+    )
 }
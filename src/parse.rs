@@ -1,41 +1,473 @@
 use alloc::{
     fmt::format,
+    string::{
+        String,
+        ToString,
+    },
     vec::Vec,
 };
 use proc_macro2::{
     Ident,
     Span,
+    TokenStream,
+    TokenTree,
+};
+use quote::{
+    format_ident,
+    quote,
+    quote_spanned,
+    ToTokens,
 };
 use syn::{
+    ext::IdentExt,
+    meta::ParseNestedMeta,
     parse::{
         Parse,
         ParseStream,
         Result,
     },
+    spanned::Spanned,
+    Attribute,
     Data,
     DeriveInput,
     Error,
     Expr,
+    ExprLit,
     Fields,
+    Generics,
     Lit,
+    LitBool,
+    LitByteStr,
+    LitChar,
+    LitInt,
     LitStr,
     Meta,
     MetaNameValue,
+    Path,
+    Token,
+    Type,
+    Visibility,
 };
 
+/// The label of a variant.
+#[derive(Clone)]
+pub enum Label {
+    /// A string literal, whose value is known when expanding the macro.
+    Lit(LitStr),
+    /// A constant expression of type `&'static str`, such as a path to a `const`.
+    Expr(Expr),
+    /// A byte string literal, for enums serialized as bytes rather than strings.
+    Bytes(LitByteStr),
+}
+
+impl Label {
+    /// Returns the string literal, if the label is one.
+    pub fn as_lit(&self) -> Option<&LitStr> {
+        match self {
+            Self::Lit(lit) => Some(lit),
+            Self::Expr(_) | Self::Bytes(_) => None,
+        }
+    }
+
+    /// Returns the byte string literal, if the label is one.
+    pub fn as_bytes(&self) -> Option<&LitByteStr> {
+        match self {
+            Self::Bytes(bytes) => Some(bytes),
+            Self::Lit(_) | Self::Expr(_) => None,
+        }
+    }
+}
+
+impl ToTokens for Label {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Lit(lit) => lit.to_tokens(tokens),
+            Self::Expr(expr) => expr.to_tokens(tokens),
+            Self::Bytes(bytes) => bytes.to_tokens(tokens),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct VariantAttrs {
-    pub string: Option<LitStr>,
-    pub aliases: Vec<LitStr>,
+    pub string: Option<Label>,
+    /// Whether the label was written as a character literal, as in `#[string = 'G']`.
+    pub char_label: bool,
+    pub aliases: Vec<Label>,
+    pub other: bool,
+    /// A glob matched against unknown strings, as in `#[string(pattern = "ERR_*")]`.
+    pub pattern: Option<LitStr>,
+    /// The order in which overlapping patterns are tried, highest first.
+    pub priority: Option<(Span, u32)>,
+    /// The identifier returned by `label_id`, in place of the position of the variant.
+    pub id: Option<(Span, u32)>,
+    /// The text written by `Display` with the `#` flag, as in `#[string("Fire", alt = "Fire
+    /// Type")]`.
+    pub alt: Option<LitStr>,
+    pub bool: Option<LitBool>,
+    pub number: Option<u64>,
+    pub docs: Vec<LitStr>,
 }
 
 impl VariantAttrs {
     pub fn new() -> Self {
         Self {
             string: None,
+            char_label: false,
             aliases: Vec::new(),
+            other: false,
+            pattern: None,
+            priority: None,
+            id: None,
+            alt: None,
+            bool: None,
+            number: None,
+            docs: Vec::new(),
         }
     }
+
+    /// Whether the variant holds the string it was parsed from instead of having a label.
+    pub fn holds_string(&self) -> bool {
+        self.other || self.pattern.is_some()
+    }
+}
+
+/// The error type generated for a failed `FromStr` conversion.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ErrorType {
+    /// A generated struct carrying the offending input.
+    Struct,
+    /// The unit type, which carries no information at all.
+    Unit,
+}
+
+/// The representation emitted when serializing.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SerializeAs {
+    /// The `#[string]` label of each variant.
+    String,
+    /// The `#[number]` of each variant.
+    Number,
+}
+
+/// The representation stored by the generated `rkyv` implementations.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveAs {
+    /// The `#[string]` label of each variant.
+    Label,
+    /// The index of each variant in declaration order.
+    Index,
+}
+
+/// The case folding under which strings are matched with the `unicase` feature.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CaseFold {
+    /// Full Unicode case folding, which may change the length of a string, as in `"ß"` to `"ss"`.
+    Full,
+    /// Simple Unicode case folding, which maps each character to a single character, so that
+    /// `"ß"` matches `"ẞ"` but not `"ss"`.
+    Simple,
+}
+
+/// The strings offered by the generated `completions` function.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Completions {
+    /// The `#[string]` label of each variant.
+    Labels,
+    /// The label and `#[alias]` attributes of each variant.
+    Aliases,
+}
+
+/// A case transformation applied to labels.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    Lower,
+    Upper,
+}
+
+impl Case {
+    pub fn apply(self, label: &LitStr) -> LitStr {
+        let value: String = match self {
+            Self::Lower => label.value().to_lowercase(),
+            Self::Upper => label.value().to_uppercase(),
+        };
+        LitStr::new(&value, label.span())
+    }
+}
+
+/// A convention that every literal label must follow.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LabelPolicy {
+    /// Labels are not empty and have no leading or trailing whitespace.
+    NonEmpty,
+    /// Labels follow [`Self::NonEmpty`] and only contain printable ASCII characters.
+    Ascii,
+    /// Labels are lowercase ASCII words separated by single hyphens, as in `"dry-run"`.
+    Kebab,
+}
+
+impl LabelPolicy {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::NonEmpty => "non_empty",
+            Self::Ascii => "ascii",
+            Self::Kebab => "kebab",
+        }
+    }
+
+    pub fn allows(self, label: &str) -> bool {
+        let non_empty = !label.is_empty() && label.trim() == label;
+        match self {
+            Self::NonEmpty => non_empty,
+            Self::Ascii => non_empty && label.chars().all(|c| c.is_ascii_graphic() || c == ' '),
+            Self::Kebab => label.split('-').all(|word| {
+                !word.is_empty()
+                    && word
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            }),
+        }
+    }
+}
+
+/// Attributes given to the enum with `#[string_enum(...)]`.
+#[derive(Clone)]
+pub struct ContainerAttrs {
+    /// The variant that unknown strings are parsed as instead of failing.
+    pub default: Option<Ident>,
+    /// The variant that null values are deserialized as.
+    pub null: Option<Ident>,
+    /// The error type of the generated `FromStr` implementation.
+    pub error: ErrorType,
+    /// Whether variants are serialized as their labels or their numbers.
+    pub serialize_as: SerializeAs,
+    /// The case transformation applied to labels when serializing.
+    pub serialize_case: Option<Case>,
+    /// Takes the label of an unlabeled variant from its doc comment.
+    pub label_from_doc: bool,
+    /// The separator between the enum name and each serialized label.
+    pub qualified: Option<String>,
+    /// The convention that every literal label and alias must follow.
+    pub label_policy: Option<LabelPolicy>,
+    /// Displays the generated code as a deprecation warning.
+    pub debug_expand: Option<Span>,
+    /// Also matches the percent-encoded spelling of each label and alias.
+    pub percent_decode: bool,
+    /// The representation stored by the generated `rkyv` implementations.
+    pub archive_as: ArchiveAs,
+    /// Marks the generated functions as `#[inline]` and parse errors as `#[cold]`.
+    pub inline: bool,
+    /// Generates a `{Enum}Seed` choosing how strings are matched for each call.
+    pub seed: Option<Span>,
+    /// Generates a `{Enum}Parser` choosing how strings are matched at runtime.
+    pub parser: Option<Span>,
+    /// Gives the generated visitor the visibility of the enum.
+    pub public_visitor: bool,
+    /// Generates `description`, which returns the doc comment of each variant.
+    pub description: bool,
+    /// Generates `completions`, which returns the strings starting with a prefix.
+    pub completions: Option<Completions>,
+    /// Generates `parse_prefix`, which parses the longest label at the start of a string.
+    pub parse_prefix: bool,
+    /// Whether `Deserialize` asks the deserializer for any value instead of a string.
+    pub self_describing: bool,
+    /// The case folding under which strings are matched with the `unicase` feature.
+    pub case_fold: CaseFold,
+    /// Rejects a label or alias used by more than one variant.
+    pub deny_overlap: bool,
+    /// Generates a test module checking that each label and alias round-trips.
+    pub generate_tests: Option<Span>,
+    /// Implements `Borrow<str>` returning the label.
+    pub borrow_str: bool,
+    /// Generates a `{VARIANT}_LABEL` constant holding the label of each labeled variant.
+    pub label_constants: bool,
+    /// Implements `Hash` over the label instead of the discriminant.
+    pub hash_by_label: bool,
+    /// Allows a label or alias to be the empty string.
+    pub allow_empty: bool,
+    /// The delimiter between labels in lists formatted and parsed by the enum.
+    pub list_delimiter: Option<LitStr>,
+    /// Generates `parse_detailed`, which also tells how a string matched.
+    pub parse_detailed: Option<Span>,
+    /// The name of the typed header implemented with the `headers` feature.
+    pub header_name: Option<LitStr>,
+    /// A function given the chance to resolve strings matching no label.
+    pub catch_all_with: Option<Path>,
+    /// The `serde_with` adapter implemented in place of `Serialize` and `Deserialize`.
+    pub serde_as: Option<Path>,
+    /// A function called with each string matching no label.
+    pub on_unknown: Option<Path>,
+    /// Another labeled enum converted into this one by its labels.
+    pub convert_from: Option<Path>,
+    /// Another labeled enum required to have every label of this one.
+    pub subset_of: Option<Path>,
+    /// A foreign enum, mirrored by this one, serialized with `#[serde(with = ...)]`.
+    pub remote: Option<Path>,
+    /// The module generated for `#[serde(with = ...)]` in place of `Serialize` and `Deserialize`.
+    pub with_module: Option<Ident>,
+    /// A prost-generated enum converted to and from by variant name.
+    pub prost: Option<Path>,
+    /// Registers the enum as a `uniffi` custom type with the `uniffi` feature.
+    pub uniffi: Option<Span>,
+    /// Exports C functions converting between variant indices and NUL-terminated labels, with
+    /// names starting with the given prefix.
+    pub c_ffi: Option<LitStr>,
+    /// Generates `serialize_as_alias`, which serializes an alias selected by its position.
+    pub alias_switch: bool,
+    /// Serialization profiles selected by name, each naming an alias position.
+    pub profiles: Vec<(LitStr, usize)>,
+    /// Implements conversions with `serde_json::Value` and generates `labels_json`.
+    pub json: bool,
+    /// Implements conversions with Python strings for `pyo3`.
+    pub pyo3: bool,
+    /// Implements conversions with `JsValue` strings for `wasm-bindgen`.
+    pub wasm_bindgen: bool,
+    /// Implements conversions with `bson::Bson` values.
+    pub bson: bool,
+    /// Implements `ToRedisArgs` and `FromRedisValue`.
+    pub redis: bool,
+    /// Implements `defmt::Format`.
+    pub defmt: bool,
+    /// Implements `minicbor::Encode` and `minicbor::Decode`.
+    pub minicbor: bool,
+    /// Implements `zvariant::Type` and conversions with `zvariant::Value`.
+    pub zbus: bool,
+    /// Generates `MAX_LABEL_LEN` and `to_heapless`.
+    pub heapless: bool,
+    /// Generates `prompt_select`.
+    pub dialoguer: bool,
+    /// Implements `rkyv` archiving.
+    pub rkyv: bool,
+    /// Implements `sea_orm::ActiveEnum`.
+    pub sea_orm: bool,
+    /// Implements poem-openapi's `Type`, `ParseFromJSON` and `ToJSON`.
+    pub poem_openapi: bool,
+}
+
+impl ContainerAttrs {
+    /// Returns the prefix written before each label, if labels are qualified with the enum name.
+    pub fn qualifier(&self, ident: &Ident) -> Option<String> {
+        self.qualified
+            .as_ref()
+            .map(|separator| format(format_args!("{ident}{separator}")))
+    }
+
+    pub fn new() -> Self {
+        Self {
+            default: None,
+            null: None,
+            error: ErrorType::Struct,
+            serialize_as: SerializeAs::String,
+            serialize_case: None,
+            label_from_doc: false,
+            qualified: None,
+            label_policy: None,
+            debug_expand: None,
+            percent_decode: false,
+            archive_as: ArchiveAs::Label,
+            inline: false,
+            seed: None,
+            parser: None,
+            public_visitor: false,
+            description: false,
+            completions: None,
+            parse_prefix: false,
+            self_describing: false,
+            case_fold: CaseFold::Full,
+            deny_overlap: false,
+            generate_tests: None,
+            borrow_str: false,
+            label_constants: false,
+            hash_by_label: false,
+            allow_empty: false,
+            list_delimiter: None,
+            parse_detailed: None,
+            header_name: None,
+            catch_all_with: None,
+            serde_as: None,
+            on_unknown: None,
+            convert_from: None,
+            subset_of: None,
+            remote: None,
+            with_module: None,
+            prost: None,
+            uniffi: None,
+            c_ffi: None,
+            alias_switch: false,
+            profiles: Vec::new(),
+            json: false,
+            pyo3: false,
+            wasm_bindgen: false,
+            bson: false,
+            redis: false,
+            defmt: false,
+            minicbor: false,
+            zbus: false,
+            heapless: false,
+            dialoguer: false,
+            rkyv: false,
+            sea_orm: false,
+            poem_openapi: false,
+        }
+    }
+}
+
+/// Folds the case of a literal label the way input is matched against it: under the selected case
+/// folding with the `unicase` feature, so that `"STRASSE"` and `"straße"` fold alike under full
+/// folding, and not at all without it.
+#[cfg(feature = "unicase")]
+fn fold_case(label: &str, case_fold: CaseFold) -> String {
+    match case_fold {
+        CaseFold::Full => unicase::UniCase::new(label).to_folded_case(),
+        CaseFold::Simple => label.chars().map(simple_fold).collect(),
+    }
+}
+
+#[cfg(not(feature = "unicase"))]
+fn fold_case(label: &str, _: CaseFold) -> String {
+    label.to_string()
+}
+
+/// Folds a character under simple Unicode case folding, as the code generated for
+/// `#[string_enum(case_fold = "simple")]` does: to the lowercase of its uppercase, unless either
+/// is more than one character. The dotless `'ı'` is the one character this would wrongly fold to
+/// `'i'`.
+#[cfg(feature = "unicase")]
+pub fn simple_fold(c: char) -> char {
+    if c == 'ı' {
+        return c;
+    }
+    let mut upper = c.to_uppercase();
+    let upper = match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => c,
+    };
+    let mut lower = upper.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => upper,
+    }
+}
+
+/// Converts a variant name such as `HttpServer` or `HTTPServer` to `HTTP_SERVER`.
+pub fn screaming_snake_case(ident: &Ident) -> String {
+    let name = format(format_args!("{ident}"));
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut constant = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lowercase)
+            {
+                constant.push('_');
+            }
+        }
+        constant.extend(c.to_uppercase());
+    }
+    constant
 }
 
 #[derive(Clone)]
@@ -45,14 +477,91 @@ pub struct Variant {
     pub fields: Fields,
 }
 
+impl Variant {
+    /// Whether the variant is labeled but carries fields, which are set to their default values
+    /// when parsed and ignored when serialized.
+    pub fn has_default_fields(&self) -> bool {
+        !self.attrs.holds_string() && !matches!(self.fields, Fields::Unit)
+    }
+
+    /// Returns the name of the constant generated with `#[string_enum(label_constants)]`, as in
+    /// `GRASS_LABEL`.
+    pub fn label_constant(&self) -> Ident {
+        format_ident!("{}_LABEL", screaming_snake_case(&self.ident.unraw()))
+    }
+
+    /// Returns the variant name followed by a pattern ignoring its fields, if any.
+    pub fn pattern(&self) -> TokenStream {
+        let ident = &self.ident;
+        match &self.fields {
+            Fields::Unit => quote! { #ident },
+            Fields::Unnamed(_) => quote! { #ident(..) },
+            Fields::Named(_) => quote! { #ident { .. } },
+        }
+    }
+
+    /// Returns the variant name followed by its fields set to their default values, if any.
+    pub fn constructor(&self) -> TokenStream {
+        let ident = &self.ident;
+        // Errors for fields without a default value point at the field type.
+        let defaults = self.fields.iter().map(|field| {
+            quote_spanned! { field.ty.span()=> ::core::default::Default::default() }
+        });
+        match &self.fields {
+            Fields::Unit => quote! { #ident },
+            Fields::Unnamed(_) => quote! { #ident(#(#defaults),*) },
+            Fields::Named(fields) => {
+                let names = fields.named.iter().map(|field| &field.ident);
+                quote! { #ident { #(#names: #defaults),* } }
+            }
+        }
+    }
+}
+
 pub struct Input {
     pub ident: Ident,
+    pub vis: Visibility,
+    pub generics: Generics,
+    pub attrs: ContainerAttrs,
+    /// The `#[allow(...)]` attributes of the enum, which also apply to the warnings of the
+    /// derives.
+    pub allow: Vec<Attribute>,
     pub variants: Vec<Variant>,
 }
 
 pub struct LabeledStringInput {
     pub ident: Ident,
+    pub vis: Visibility,
+    pub generics: Generics,
+    pub attrs: ContainerAttrs,
     pub variants: Vec<Variant>,
+    /// Whether the labels are byte strings, serialized as bytes rather than strings.
+    pub byte_labels: bool,
+    /// Whether every label is a character literal, so that variants are serialized as characters.
+    pub char_labels: bool,
+}
+
+/// Collects errors, so that every problem with the input is reported in a single expansion.
+struct Errors(Option<Error>);
+
+impl Errors {
+    fn new() -> Self {
+        Self(None)
+    }
+
+    fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
 }
 
 fn get_string_literal_from_name_value_attr(
@@ -79,83 +588,1350 @@ fn get_string_literal_from_name_value_attr(
     }
 }
 
-fn parse_variant_attrs(span: Span, variant: &syn::Variant) -> Result<VariantAttrs> {
-    let mut attrs = VariantAttrs::new();
-    for attr in &variant.attrs {
-        if let Meta::NameValue(name_value) = &attr.meta {
+/// Reads a label, which may be a macro invocation expanding to a string literal, such as `concat!`
+/// or `env!`, or a byte string literal.
+fn get_label_from_name_value_attr(
+    span: Span,
+    attribute_name: &str,
+    name_value: &MetaNameValue,
+) -> Result<Label> {
+    match &name_value.value {
+        Expr::Macro(_) => Ok(Label::Expr(name_value.value.clone())),
+        Expr::Lit(ExprLit {
+            lit: Lit::ByteStr(bytes),
+            ..
+        }) => Ok(Label::Bytes(bytes.clone())),
+        Expr::Lit(ExprLit {
+            lit: Lit::Char(char),
+            ..
+        }) => Ok(char_label(char)),
+        _ => get_string_literal_from_name_value_attr(span, attribute_name, name_value)
+            .map(Label::Lit),
+    }
+}
+
+fn get_integer_from_name_value_attr(
+    span: Span,
+    attribute_name: &str,
+    name_value: &MetaNameValue,
+) -> Result<u64> {
+    match &name_value.value {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Int(int) => int.base10_parse::<u64>(),
+            _ => Err(Error::new(
+                span,
+                format(format_args!(
+                    "\"{attribute_name}\" attribute must be an integer literal"
+                )),
+            )),
+        },
+        _ => Err(Error::new(
+            span,
+            format(format_args!(
+                "\"{attribute_name}\" attribute must be an integer literal"
+            )),
+        )),
+    }
+}
+
+/// Returns the one-character string label for a character literal.
+fn char_label(char: &LitChar) -> Label {
+    Label::Lit(LitStr::new(
+        char.value().encode_utf8(&mut [0; 4]),
+        char.span(),
+    ))
+}
+
+/// Parses a label given in the list form of the `#[string(...)]` attribute.
+fn parse_label(input: ParseStream) -> Result<Label> {
+    match input.parse::<Expr>()? {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Ok(Label::Lit(lit)),
+        Expr::Lit(ExprLit {
+            lit: Lit::ByteStr(bytes),
+            ..
+        }) => Ok(Label::Bytes(bytes)),
+        Expr::Lit(ExprLit {
+            lit: Lit::Char(char),
+            ..
+        }) => Ok(char_label(&char)),
+        Expr::Lit(expr) => Err(Error::new(
+            expr.span(),
+            "\"string\" attribute must be a string literal or a constant expression",
+        )),
+        expr => Ok(Label::Expr(expr)),
+    }
+}
+
+fn duplicate_label_error(span: Span) -> Error {
+    Error::new(
+        span,
+        "duplicate \"string\" attribute; use #[alias = ...] to accept another string",
+    )
+}
+
+fn parse_variant_attr(span: Span, attr: &Attribute, attrs: &mut VariantAttrs) -> Result<()> {
+    match &attr.meta {
+        Meta::NameValue(name_value) => {
             if name_value.path.is_ident("string") {
-                attrs.string = Some(get_string_literal_from_name_value_attr(
-                    span, "string", name_value,
-                )?)
+                if attrs.string.is_some() {
+                    return Err(duplicate_label_error(name_value.span()));
+                }
+                attrs.char_label = matches!(
+                    name_value.value,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Char(_),
+                        ..
+                    })
+                );
+                attrs.string = Some(get_label_from_name_value_attr(span, "string", name_value)?)
             } else if name_value.path.is_ident("alias") {
-                attrs.aliases.push(get_string_literal_from_name_value_attr(
-                    span, "alias", name_value,
+                attrs
+                    .aliases
+                    .push(get_label_from_name_value_attr(span, "alias", name_value)?)
+            } else if name_value.path.is_ident("number") {
+                attrs.number = Some(get_integer_from_name_value_attr(
+                    span, "number", name_value,
                 )?)
+            } else if name_value.path.is_ident("doc") {
+                if let Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) = &name_value.value
+                {
+                    attrs.docs.push(doc.clone());
+                }
             }
         }
+        Meta::List(list) if list.path.is_ident("string") => {
+            list.parse_args_with(|input: ParseStream| {
+                while !input.is_empty() {
+                    let option = input.fork().parse::<Ident>().ok();
+                    if option.as_ref().is_some_and(|option| option == "bool") {
+                        input.parse::<Ident>()?;
+                        input.parse::<Token![=]>()?;
+                        attrs.bool = Some(input.parse()?);
+                    } else if option.as_ref().is_some_and(|option| option == "other") {
+                        input.parse::<Ident>()?;
+                        attrs.other = true;
+                    } else if option.as_ref().is_some_and(|option| option == "pattern") {
+                        input.parse::<Ident>()?;
+                        input.parse::<Token![=]>()?;
+                        attrs.pattern = Some(input.parse()?);
+                    } else if option.as_ref().is_some_and(|option| option == "priority") {
+                        input.parse::<Ident>()?;
+                        input.parse::<Token![=]>()?;
+                        let priority = input.parse::<LitInt>()?;
+                        attrs.priority = Some((priority.span(), priority.base10_parse()?));
+                    } else if option.as_ref().is_some_and(|option| option == "id") {
+                        input.parse::<Ident>()?;
+                        input.parse::<Token![=]>()?;
+                        let id = input.parse::<LitInt>()?;
+                        attrs.id = Some((id.span(), id.base10_parse()?));
+                    } else if option.as_ref().is_some_and(|option| option == "alt") {
+                        input.parse::<Ident>()?;
+                        input.parse::<Token![=]>()?;
+                        attrs.alt = Some(input.parse()?);
+                    } else {
+                        if attrs.string.is_some() {
+                            return Err(duplicate_label_error(input.span()));
+                        }
+                        attrs.char_label = input.peek(LitChar);
+                        attrs.string = Some(parse_label(input)?);
+                    }
+                    if !input.is_empty() {
+                        input.parse::<Token![,]>()?;
+                    }
+                }
+                Ok(())
+            })?;
+        }
+        _ => (),
     }
+    Ok(())
+}
+
+fn parse_variant_attrs(span: Span, variant: &syn::Variant) -> Result<VariantAttrs> {
+    let mut attrs = VariantAttrs::new();
+    let mut errors = Errors::new();
+    for attr in &variant.attrs {
+        if let Err(error) = parse_variant_attr(span, attr, &mut attrs) {
+            errors.push(error);
+        }
+    }
+    errors.finish()?;
     Ok(attrs)
 }
 
+fn parse_container_attrs(attrs: &[Attribute]) -> Result<ContainerAttrs> {
+    let mut container_attrs = ContainerAttrs::new();
+    let mut archive_as = None;
+    let mut errors = Errors::new();
+    for attr in attrs {
+        if !attr.path().is_ident("string_enum") {
+            continue;
+        }
+        let result = attr.parse_nested_meta(|meta| {
+            if let Err(error) = parse_container_attr(&meta, &mut container_attrs, &mut archive_as) {
+                errors.push(error);
+                // Skip the rest of the attribute, so that the following ones are still checked.
+                while !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                    meta.input.parse::<TokenTree>()?;
+                }
+            }
+            Ok(())
+        });
+        if let Err(error) = result {
+            errors.push(error);
+        }
+    }
+    if let Some(archive_as) = archive_as {
+        if !container_attrs.rkyv {
+            errors.push(Error::new(
+                archive_as,
+                "\"archive_as\" requires the \"rkyv\" attribute",
+            ));
+        }
+    }
+    errors.finish()?;
+    Ok(container_attrs)
+}
+
+fn parse_container_attr(
+    meta: &ParseNestedMeta,
+    container_attrs: &mut ContainerAttrs,
+    archive_as: &mut Option<Span>,
+) -> Result<()> {
+    if meta.path.is_ident("default") {
+        container_attrs.default = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("serde_as") {
+        if !cfg!(feature = "serde_with") {
+            return Err(
+                meta.error("\"serde_as\" requires the \"serde_with\" feature of serde_string_enum")
+            );
+        }
+        container_attrs.serde_as = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("catch_all_with") {
+        container_attrs.catch_all_with = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("convert_from") {
+        container_attrs.convert_from = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("prost") {
+        container_attrs.prost = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("c_ffi") {
+        if !meta.input.peek(Token![=]) {
+            return Err(
+                meta.error("\"c_ffi\" requires a symbol prefix, as in `c_ffi = \"mylib_\"`")
+            );
+        }
+        let prefix = meta.value()?.parse::<LitStr>()?;
+        let value = prefix.value();
+        if !value.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            || !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(Error::new(
+                prefix.span(),
+                "\"c_ffi\" prefix must start a C identifier",
+            ));
+        }
+        container_attrs.c_ffi = Some(prefix);
+        Ok(())
+    } else if meta.path.is_ident("json") {
+        if !cfg!(any(feature = "std", feature = "alloc")) {
+            return Err(meta.error("\"json\" requires the \"alloc\" feature of serde_string_enum"));
+        }
+        container_attrs.json = true;
+        Ok(())
+    } else if meta.path.is_ident("pyo3") {
+        if !cfg!(feature = "std") {
+            return Err(meta.error("\"pyo3\" requires the \"std\" feature of serde_string_enum"));
+        }
+        container_attrs.pyo3 = true;
+        Ok(())
+    } else if meta.path.is_ident("wasm_bindgen") {
+        if !cfg!(any(feature = "std", feature = "alloc")) {
+            return Err(
+                meta.error("\"wasm_bindgen\" requires the \"alloc\" feature of serde_string_enum")
+            );
+        }
+        container_attrs.wasm_bindgen = true;
+        Ok(())
+    } else if meta.path.is_ident("bson") {
+        if !cfg!(feature = "std") {
+            return Err(meta.error("\"bson\" requires the \"std\" feature of serde_string_enum"));
+        }
+        container_attrs.bson = true;
+        Ok(())
+    } else if meta.path.is_ident("redis") {
+        if !cfg!(feature = "std") {
+            return Err(meta.error("\"redis\" requires the \"std\" feature of serde_string_enum"));
+        }
+        container_attrs.redis = true;
+        Ok(())
+    } else if meta.path.is_ident("defmt") {
+        container_attrs.defmt = true;
+        Ok(())
+    } else if meta.path.is_ident("minicbor") {
+        container_attrs.minicbor = true;
+        Ok(())
+    } else if meta.path.is_ident("zbus") {
+        if !cfg!(feature = "std") {
+            return Err(meta.error("\"zbus\" requires the \"std\" feature of serde_string_enum"));
+        }
+        container_attrs.zbus = true;
+        Ok(())
+    } else if meta.path.is_ident("heapless") {
+        container_attrs.heapless = true;
+        Ok(())
+    } else if meta.path.is_ident("dialoguer") {
+        if !cfg!(feature = "std") {
+            return Err(
+                meta.error("\"dialoguer\" requires the \"std\" feature of serde_string_enum")
+            );
+        }
+        container_attrs.dialoguer = true;
+        Ok(())
+    } else if meta.path.is_ident("rkyv") {
+        container_attrs.rkyv = true;
+        Ok(())
+    } else if meta.path.is_ident("sea_orm") {
+        if !cfg!(feature = "std") {
+            return Err(meta.error("\"sea_orm\" requires the \"std\" feature of serde_string_enum"));
+        }
+        container_attrs.sea_orm = true;
+        Ok(())
+    } else if meta.path.is_ident("poem_openapi") {
+        if !cfg!(feature = "std") {
+            return Err(
+                meta.error("\"poem_openapi\" requires the \"std\" feature of serde_string_enum")
+            );
+        }
+        container_attrs.poem_openapi = true;
+        Ok(())
+    } else if meta.path.is_ident("uniffi") {
+        if !cfg!(feature = "uniffi") {
+            return Err(
+                meta.error("\"uniffi\" requires the \"uniffi\" feature of serde_string_enum")
+            );
+        }
+        container_attrs.uniffi = Some(meta.path.span());
+        Ok(())
+    } else if meta.path.is_ident("with_module") {
+        container_attrs.with_module = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("remote") {
+        let value: LitStr = meta.value()?.parse()?;
+        container_attrs.remote = Some(value.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("subset_of") {
+        container_attrs.subset_of = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("on_unknown") {
+        container_attrs.on_unknown = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("null") {
+        container_attrs.null = Some(meta.value()?.parse()?);
+        Ok(())
+    } else if meta.path.is_ident("error") {
+        let value: LitStr = meta.value()?.parse()?;
+        container_attrs.error = match value.value().as_str() {
+            "struct" => ErrorType::Struct,
+            "unit" => ErrorType::Unit,
+            _ => {
+                return Err(Error::new(
+                    value.span(),
+                    "\"error\" must be one of \"struct\" or \"unit\"",
+                ))
+            }
+        };
+        Ok(())
+    } else if meta.path.is_ident("serialize_as") {
+        let value: LitStr = meta.value()?.parse()?;
+        container_attrs.serialize_as = match value.value().as_str() {
+            "string" => SerializeAs::String,
+            "number" => SerializeAs::Number,
+            _ => {
+                return Err(Error::new(
+                    value.span(),
+                    "\"serialize_as\" must be one of \"string\" or \"number\"",
+                ))
+            }
+        };
+        Ok(())
+    } else if meta.path.is_ident("debug_expand") {
+        container_attrs.debug_expand = Some(meta.path.span());
+        Ok(())
+    } else if meta.path.is_ident("archive_as") {
+        *archive_as = Some(meta.path.span());
+        let value: LitStr = meta.value()?.parse()?;
+        container_attrs.archive_as = match value.value().as_str() {
+            "label" => ArchiveAs::Label,
+            "index" => ArchiveAs::Index,
+            _ => {
+                return Err(Error::new(
+                    value.span(),
+                    "\"archive_as\" must be one of \"label\" or \"index\"",
+                ))
+            }
+        };
+        Ok(())
+    } else if meta.path.is_ident("case_fold") {
+        if !cfg!(feature = "unicase") {
+            return Err(
+                meta.error("\"case_fold\" requires the \"unicase\" feature of serde_string_enum")
+            );
+        }
+        let value: LitStr = meta.value()?.parse()?;
+        container_attrs.case_fold = match value.value().as_str() {
+            "full" => CaseFold::Full,
+            "simple" => CaseFold::Simple,
+            _ => {
+                return Err(Error::new(
+                    value.span(),
+                    "\"case_fold\" must be one of \"full\" or \"simple\"",
+                ))
+            }
+        };
+        Ok(())
+    } else if meta.path.is_ident("completions") {
+        container_attrs.completions = Some(if meta.input.peek(Token![=]) {
+            let value: LitStr = meta.value()?.parse()?;
+            match value.value().as_str() {
+                "labels" => Completions::Labels,
+                "aliases" => Completions::Aliases,
+                _ => {
+                    return Err(Error::new(
+                        value.span(),
+                        "\"completions\" must be one of \"labels\" or \"aliases\"",
+                    ))
+                }
+            }
+        } else {
+            Completions::Labels
+        });
+        Ok(())
+    } else if meta.path.is_ident("description") {
+        container_attrs.description = true;
+        Ok(())
+    } else if meta.path.is_ident("public_visitor") {
+        container_attrs.public_visitor = true;
+        Ok(())
+    } else if meta.path.is_ident("seed") {
+        container_attrs.seed = Some(meta.path.span());
+        Ok(())
+    } else if meta.path.is_ident("parser") {
+        container_attrs.parser = Some(meta.path.span());
+        Ok(())
+    } else if meta.path.is_ident("generate_tests") {
+        container_attrs.generate_tests = Some(meta.path.span());
+        Ok(())
+    } else if meta.path.is_ident("borrow_str") {
+        container_attrs.borrow_str = true;
+        Ok(())
+    } else if meta.path.is_ident("label_constants") {
+        container_attrs.label_constants = true;
+        Ok(())
+    } else if meta.path.is_ident("hash_by_label") {
+        container_attrs.hash_by_label = true;
+        Ok(())
+    } else if meta.path.is_ident("list_delimiter") {
+        if !cfg!(any(feature = "std", feature = "alloc")) {
+            return Err(meta
+                .error("\"list_delimiter\" requires the \"alloc\" feature of serde_string_enum"));
+        }
+        let value: LitStr = meta.value()?.parse()?;
+        if value.value().is_empty() {
+            return Err(Error::new(
+                value.span(),
+                "\"list_delimiter\" must not be empty",
+            ));
+        }
+        container_attrs.list_delimiter = Some(value);
+        Ok(())
+    } else if meta.path.is_ident("alias_switch") {
+        container_attrs.alias_switch = true;
+        Ok(())
+    } else if meta.path.is_ident("profile") {
+        if !cfg!(feature = "std") {
+            return Err(meta.error("\"profile\" requires the \"std\" feature of serde_string_enum"));
+        }
+        meta.parse_nested_meta(|profile| {
+            let name = match profile.path.get_ident() {
+                Some(name) => name,
+                None => return Err(profile.error("expected profile name")),
+            };
+            let index = profile.value()?.parse::<LitInt>()?.base10_parse()?;
+            container_attrs
+                .profiles
+                .push((LitStr::new(&name.to_string(), name.span()), index));
+            Ok(())
+        })
+    } else if meta.path.is_ident("allow_empty") {
+        container_attrs.allow_empty = true;
+        Ok(())
+    } else if meta.path.is_ident("deny_overlap") {
+        container_attrs.deny_overlap = true;
+        Ok(())
+    } else if meta.path.is_ident("header_name") {
+        if !cfg!(feature = "headers") {
+            return Err(
+                meta.error("\"header_name\" requires the \"headers\" feature of serde_string_enum")
+            );
+        }
+        let value: LitStr = meta.value()?.parse()?;
+        let name = value.value();
+        if name.is_empty()
+            || !name.bytes().all(|b| {
+                b.is_ascii_lowercase() || b.is_ascii_digit() || b"!#$%&'*+-.^_`|~".contains(&b)
+            })
+        {
+            return Err(Error::new(
+                value.span(),
+                "\"header_name\" must be a lowercase HTTP header name",
+            ));
+        }
+        container_attrs.header_name = Some(value);
+        Ok(())
+    } else if meta.path.is_ident("parse_detailed") {
+        container_attrs.parse_detailed = Some(meta.path.span());
+        Ok(())
+    } else if meta.path.is_ident("self_describing") {
+        container_attrs.self_describing = true;
+        Ok(())
+    } else if meta.path.is_ident("parse_prefix") {
+        container_attrs.parse_prefix = true;
+        Ok(())
+    } else if meta.path.is_ident("inline") {
+        container_attrs.inline = true;
+        Ok(())
+    } else if meta.path.is_ident("label_from_doc") {
+        container_attrs.label_from_doc = true;
+        Ok(())
+    } else if meta.path.is_ident("percent_decode") {
+        if !cfg!(feature = "percent_decode") {
+            return Err(meta.error(
+                "\"percent_decode\" requires the \"percent_decode\" feature of serde_string_enum",
+            ));
+        }
+        container_attrs.percent_decode = true;
+        Ok(())
+    } else if meta.path.is_ident("qualified") {
+        container_attrs.qualified = Some(if meta.input.peek(Token![=]) {
+            meta.value()?.parse::<LitStr>()?.value()
+        } else {
+            String::from("::")
+        });
+        Ok(())
+    } else if meta.path.is_ident("label_policy") {
+        let value: LitStr = meta.value()?.parse()?;
+        container_attrs.label_policy = Some(match value.value().as_str() {
+            "non_empty" => LabelPolicy::NonEmpty,
+            "ascii" => LabelPolicy::Ascii,
+            "kebab" => LabelPolicy::Kebab,
+            _ => {
+                return Err(Error::new(
+                    value.span(),
+                    "\"label_policy\" must be one of \"non_empty\", \"ascii\", or \"kebab\"",
+                ))
+            }
+        });
+        Ok(())
+    } else if meta.path.is_ident("serialize_case") {
+        let value: LitStr = meta.value()?.parse()?;
+        container_attrs.serialize_case = Some(match value.value().as_str() {
+            "lowercase" => Case::Lower,
+            "UPPERCASE" => Case::Upper,
+            _ => {
+                return Err(Error::new(
+                    value.span(),
+                    "\"serialize_case\" must be one of \"lowercase\" or \"UPPERCASE\"",
+                ))
+            }
+        });
+        Ok(())
+    } else {
+        Err(meta.error("unsupported \"string_enum\" attribute"))
+    }
+}
+
 impl Parse for Input {
     fn parse(input: ParseStream) -> Result<Self> {
         let call_site = Span::call_site();
         let derive_input = DeriveInput::parse(input)?;
+        let mut errors = Errors::new();
+        let attrs = parse_container_attrs(&derive_input.attrs).unwrap_or_else(|error| {
+            errors.push(error);
+            ContainerAttrs::new()
+        });
         let data = match derive_input.data {
             Data::Enum(data) => data,
-            _ => return Err(Error::new(call_site, "input must be an enum")),
+            _ => {
+                return Err(Error::new(
+                    derive_input.ident.span(),
+                    "input must be an enum",
+                ))
+            }
         };
 
         let variants = data
             .variants
             .into_iter()
             .map(|variant| {
-                let attrs = parse_variant_attrs(call_site, &variant)?;
-                Ok(Variant {
+                let attrs = parse_variant_attrs(call_site, &variant).unwrap_or_else(|error| {
+                    errors.push(error);
+                    VariantAttrs::new()
+                });
+                Variant {
                     ident: variant.ident,
                     attrs,
                     fields: variant.fields,
-                })
+                }
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Vec<_>>();
+        errors.finish()?;
 
         if variants.is_empty() {
             return Err(Error::new(call_site, "enum must have at least one variant"));
         }
 
+        let allow = derive_input
+            .attrs
+            .into_iter()
+            .filter(|attr| attr.path().is_ident("allow"))
+            .collect();
+
         Ok(Input {
             ident: derive_input.ident,
+            vis: derive_input.vis,
+            generics: derive_input.generics,
+            attrs,
+            allow,
             variants,
         })
     }
 }
 
+impl Input {
+    /// Returns the first label or alias, which a derive using the enum's own implementation
+    /// ignores.
+    pub fn first_label(&self) -> Option<&Label> {
+        self.variants
+            .iter()
+            .flat_map(|variant| variant.attrs.string.iter().chain(&variant.attrs.aliases))
+            .next()
+    }
+}
+
 impl Parse for LabeledStringInput {
     fn parse(input: ParseStream) -> Result<Self> {
         let call_site = Span::call_site();
-        let input = Input::parse(input)?;
+        let mut input = Input::parse(input)?;
+        let mut errors = Errors::new();
 
-        if !input.variants.iter().all(|variant| match variant.fields {
-            Fields::Unit => true,
-            _ => false,
-        }) {
-            return Err(Error::new(call_site, "all variants must be a unit variant"));
+        if input.attrs.label_from_doc {
+            for variant in &mut input.variants {
+                if variant.attrs.string.is_none() {
+                    variant.attrs.string = variant.attrs.docs.iter().find_map(|doc| {
+                        let line = doc.value();
+                        let line = line.trim();
+                        (!line.is_empty()).then(|| Label::Lit(LitStr::new(line, doc.span())))
+                    });
+                }
+            }
         }
 
-        if !input
+        if let Some(policy) = input.attrs.label_policy {
+            for label in input.variants.iter().flat_map(|variant| {
+                variant
+                    .attrs
+                    .string
+                    .iter()
+                    .chain(&variant.attrs.aliases)
+                    .filter_map(Label::as_lit)
+            }) {
+                if !policy.allows(&label.value()) {
+                    errors.push(Error::new(
+                        label.span(),
+                        format(format_args!(
+                            "label {:?} does not follow the \"{}\" label policy",
+                            label.value(),
+                            policy.name(),
+                        )),
+                    ));
+                }
+            }
+        }
+
+        if !input.attrs.allow_empty {
+            for label in input
+                .variants
+                .iter()
+                .flat_map(|variant| variant.attrs.string.iter().chain(&variant.attrs.aliases))
+            {
+                let span = match label {
+                    Label::Lit(lit) if lit.value().is_empty() => lit.span(),
+                    Label::Bytes(bytes) if bytes.value().is_empty() => bytes.span(),
+                    _ => continue,
+                };
+                errors.push(Error::new(
+                    span,
+                    "label is empty; use #[string_enum(allow_empty)] to allow empty labels",
+                ));
+            }
+        }
+
+        let mut others = input.variants.iter().filter(|variant| variant.attrs.other);
+        let other = others.next();
+        if others.next().is_some() {
+            errors.push(Error::new(
+                call_site,
+                "only one variant may have the \"other\" attribute",
+            ));
+        }
+
+        if let Some(other) = other {
+            if !matches!(&other.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1) {
+                errors.push(Error::new(
+                    other.ident.span(),
+                    "\"other\" variant must have exactly one unnamed field",
+                ));
+            }
+            if other.attrs.string.is_some()
+                || !other.attrs.aliases.is_empty()
+                || other.attrs.bool.is_some()
+                || other.attrs.number.is_some()
+            {
+                errors.push(Error::new(
+                    other.ident.span(),
+                    "\"other\" variant cannot have labels",
+                ));
+            }
+            if input.attrs.default.is_some() {
+                errors.push(Error::new(
+                    call_site,
+                    "\"default\" cannot be used with an \"other\" variant",
+                ));
+            }
+            if let Some(delimiter) = &input.attrs.list_delimiter {
+                if other
+                    .fields
+                    .iter()
+                    .any(|field| matches!(field.ty, Type::Reference(_)))
+                {
+                    errors.push(Error::new(
+                        delimiter.span(),
+                        "\"list_delimiter\" requires the \"other\" variant to own its string",
+                    ));
+                }
+            }
+            if let Some(parse_detailed) = input.attrs.parse_detailed {
+                if other
+                    .fields
+                    .iter()
+                    .any(|field| matches!(field.ty, Type::Reference(_)))
+                {
+                    errors.push(Error::new(
+                        parse_detailed,
+                        "\"parse_detailed\" requires the \"other\" variant to own its string",
+                    ));
+                }
+            }
+        }
+
+        for variant in input
             .variants
             .iter()
-            .all(|variant| variant.attrs.string.is_some())
+            .filter(|variant| variant.attrs.pattern.is_some())
         {
-            return Err(Error::new(
+            let pattern = variant.attrs.pattern.as_ref().unwrap();
+            match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    if matches!(fields.unnamed[0].ty, Type::Reference(_)) {
+                        errors.push(Error::new(
+                            variant.ident.span(),
+                            "\"pattern\" variant cannot borrow from the input",
+                        ));
+                    }
+                }
+                _ => errors.push(Error::new(
+                    variant.ident.span(),
+                    "\"pattern\" variant must have exactly one unnamed field",
+                )),
+            }
+            if variant.attrs.other
+                || variant.attrs.string.is_some()
+                || !variant.attrs.aliases.is_empty()
+                || variant.attrs.bool.is_some()
+                || variant.attrs.number.is_some()
+            {
+                errors.push(Error::new(
+                    variant.ident.span(),
+                    "\"pattern\" variant cannot have labels",
+                ));
+            }
+            if !cfg!(any(feature = "alloc", feature = "std")) {
+                errors.push(Error::new(
+                    pattern.span(),
+                    "\"pattern\" requires the \"alloc\" feature of serde_string_enum",
+                ));
+            }
+            if input.attrs.seed.is_some() {
+                errors.push(Error::new(
+                    pattern.span(),
+                    "\"pattern\" cannot be used with \"seed\"",
+                ));
+            }
+            if input.attrs.parser.is_some() {
+                errors.push(Error::new(
+                    pattern.span(),
+                    "\"pattern\" cannot be used with \"parser\"",
+                ));
+            }
+        }
+
+        let mut ids = Vec::new();
+        for (i, variant) in input.variants.iter().enumerate() {
+            if variant.attrs.holds_string() {
+                if let Some((span, _)) = variant.attrs.id {
+                    errors.push(Error::new(
+                        span,
+                        "\"id\" can only be used on a labeled variant",
+                    ));
+                }
+                continue;
+            }
+            let (span, id) = variant.attrs.id.unwrap_or((variant.ident.span(), i as u32));
+            if ids.contains(&id) {
+                errors.push(Error::new(
+                    span,
+                    format(format_args!("only one variant may have the id {id}")),
+                ));
+            }
+            ids.push(id);
+        }
+
+        if input.attrs.label_constants {
+            let mut constants = Vec::<(Ident, &Ident)>::new();
+            for variant in input
+                .variants
+                .iter()
+                .filter(|variant| !variant.attrs.holds_string())
+            {
+                let constant = variant.label_constant();
+                match constants.iter().find(|(claimed, _)| *claimed == constant) {
+                    Some((_, owner)) => errors.push(Error::new(
+                        variant.ident.span(),
+                        format(format_args!(
+                            "constant `{constant}` is already generated for variant `{owner}`"
+                        )),
+                    )),
+                    None => constants.push((constant, &variant.ident)),
+                }
+            }
+        }
+
+        for variant in &input.variants {
+            if let Some(alt) = &variant.attrs.alt {
+                if variant.attrs.holds_string() {
+                    errors.push(Error::new(
+                        alt.span(),
+                        "\"alt\" can only be used on a labeled variant",
+                    ));
+                }
+            }
+        }
+
+        for variant in &input.variants {
+            if let Some((span, _)) = variant.attrs.priority {
+                if variant.attrs.pattern.is_none() {
+                    errors.push(Error::new(
+                        span,
+                        "\"priority\" can only be used on a \"pattern\" variant",
+                    ));
+                }
+            }
+        }
+
+        for variant in &input.variants {
+            if !variant.attrs.holds_string() && variant.attrs.string.is_none() {
+                errors.push(Error::new(
+                    variant.ident.span(),
+                    "variant must have \"string\" attribute",
+                ));
+            }
+        }
+
+        if input.attrs.serialize_case.is_some() {
+            // Aliases are also serialized when an alias can be selected.
+            let serializes_aliases = input.attrs.alias_switch || !input.attrs.profiles.is_empty();
+            for label in input.variants.iter().flat_map(|variant| {
+                variant
+                    .attrs
+                    .string
+                    .iter()
+                    .chain(variant.attrs.aliases.iter().filter(|_| serializes_aliases))
+            }) {
+                if let Label::Expr(expr) = label {
+                    errors.push(Error::new(
+                        expr.span(),
+                        "\"serialize_case\" requires all labels to be string literals",
+                    ));
+                }
+            }
+        }
+
+        if input.attrs.percent_decode {
+            for label in input
+                .variants
+                .iter()
+                .flat_map(|variant| variant.attrs.string.iter().chain(&variant.attrs.aliases))
+            {
+                if let Label::Expr(expr) = label {
+                    errors.push(Error::new(
+                        expr.span(),
+                        "\"percent_decode\" requires all labels and aliases to be string literals",
+                    ));
+                }
+            }
+        }
+
+        for value in [false, true] {
+            let mut variants = input
+                .variants
+                .iter()
+                .filter_map(|variant| variant.attrs.bool.as_ref())
+                .filter(|bool| bool.value == value);
+            variants.next();
+            for duplicate in variants {
+                errors.push(Error::new(
+                    duplicate.span,
+                    format(format_args!(
+                        "only one variant may be mapped to the boolean value {value}"
+                    )),
+                ));
+            }
+        }
+
+        if input.attrs.serialize_as == SerializeAs::Number {
+            for variant in &input.variants {
+                if variant.attrs.number.is_none() {
+                    errors.push(Error::new(
+                        variant.ident.span(),
+                        "variant must have \"number\" attribute to serialize as numbers",
+                    ));
+                }
+                if input.attrs.bson
+                    && variant
+                        .attrs
+                        .number
+                        .is_some_and(|number| i64::try_from(number).is_err())
+                {
+                    errors.push(Error::new(
+                        variant.ident.span(),
+                        "\"bson\" cannot serialize numbers greater than i64::MAX",
+                    ));
+                }
+            }
+        }
+
+        for (i, variant) in input.variants.iter().enumerate() {
+            if let Some(number) = variant.attrs.number {
+                if input.variants[..i]
+                    .iter()
+                    .any(|previous| previous.attrs.number == Some(number))
+                {
+                    errors.push(Error::new(
+                        variant.ident.span(),
+                        format(format_args!(
+                            "only one variant may be mapped to the number {number}"
+                        )),
+                    ));
+                }
+            }
+        }
+
+        if input.attrs.deny_overlap {
+            // Spellings claimed by earlier variants, folded the way they are matched.
+            let mut claimed = Vec::<(Vec<u8>, &Ident)>::new();
+            for variant in &input.variants {
+                for label in variant.attrs.string.iter().chain(&variant.attrs.aliases) {
+                    let (span, spelling) = match label {
+                        Label::Lit(lit) => (
+                            lit.span(),
+                            fold_case(&lit.value(), input.attrs.case_fold).into_bytes(),
+                        ),
+                        Label::Bytes(bytes) => (bytes.span(), bytes.value()),
+                        Label::Expr(_) => continue,
+                    };
+                    match claimed.iter().find(|(claimed, _)| *claimed == spelling) {
+                        Some((_, owner)) if **owner != variant.ident => {
+                            errors.push(Error::new(
+                                span,
+                                format(format_args!("label is already used by variant `{owner}`")),
+                            ));
+                        }
+                        Some(_) => (),
+                        None => claimed.push((spelling, &variant.ident)),
+                    }
+                }
+            }
+        }
+
+        for (attribute_name, variant_ident) in [
+            ("default", &input.attrs.default),
+            ("null", &input.attrs.null),
+        ] {
+            if let Some(variant_ident) = variant_ident {
+                if !input
+                    .variants
+                    .iter()
+                    .any(|variant| !variant.attrs.holds_string() && variant.ident == *variant_ident)
+                {
+                    errors.push(Error::new(
+                        variant_ident.span(),
+                        format(format_args!(
+                            "\"{attribute_name}\" must name a labeled variant of the enum"
+                        )),
+                    ));
+                }
+            }
+        }
+
+        let labels = || {
+            input
+                .variants
+                .iter()
+                .flat_map(|variant| variant.attrs.string.iter().chain(&variant.attrs.aliases))
+        };
+        let byte_labels = labels().any(|label| label.as_bytes().is_some());
+        if byte_labels {
+            for label in labels().filter(|label| label.as_bytes().is_none()) {
+                errors.push(Error::new(
+                    label.to_token_stream().span(),
+                    "byte string labels cannot be mixed with string labels",
+                ));
+            }
+            for (attribute_name, used) in [
+                ("other", other.is_some()),
+                ("null", input.attrs.null.is_some()),
+                ("qualified", input.attrs.qualified.is_some()),
+                (
+                    "serialize_as",
+                    input.attrs.serialize_as != SerializeAs::String,
+                ),
+                ("serialize_case", input.attrs.serialize_case.is_some()),
+                ("percent_decode", input.attrs.percent_decode),
+                ("seed", input.attrs.seed.is_some()),
+                ("parser", input.attrs.parser.is_some()),
+                ("completions", input.attrs.completions.is_some()),
+                ("generate_tests", input.attrs.generate_tests.is_some()),
+                (
+                    "pattern",
+                    input
+                        .variants
+                        .iter()
+                        .any(|variant| variant.attrs.pattern.is_some()),
+                ),
+                ("parse_prefix", input.attrs.parse_prefix),
+                ("self_describing", input.attrs.self_describing),
+                (
+                    "alt",
+                    input
+                        .variants
+                        .iter()
+                        .any(|variant| variant.attrs.alt.is_some()),
+                ),
+                ("borrow_str", input.attrs.borrow_str),
+                ("label_constants", input.attrs.label_constants),
+                ("hash_by_label", input.attrs.hash_by_label),
+                ("list_delimiter", input.attrs.list_delimiter.is_some()),
+                ("parse_detailed", input.attrs.parse_detailed.is_some()),
+                ("header_name", input.attrs.header_name.is_some()),
+                ("catch_all_with", input.attrs.catch_all_with.is_some()),
+                ("serde_as", input.attrs.serde_as.is_some()),
+                ("on_unknown", input.attrs.on_unknown.is_some()),
+                ("convert_from", input.attrs.convert_from.is_some()),
+                ("subset_of", input.attrs.subset_of.is_some()),
+                ("remote", input.attrs.remote.is_some()),
+                ("with_module", input.attrs.with_module.is_some()),
+                ("prost", input.attrs.prost.is_some()),
+                ("uniffi", input.attrs.uniffi.is_some()),
+                ("c_ffi", input.attrs.c_ffi.is_some()),
+                ("alias_switch", input.attrs.alias_switch),
+                ("json", input.attrs.json),
+                ("pyo3", input.attrs.pyo3),
+                ("wasm_bindgen", input.attrs.wasm_bindgen),
+                ("bson", input.attrs.bson),
+                ("redis", input.attrs.redis),
+                ("defmt", input.attrs.defmt),
+                ("minicbor", input.attrs.minicbor),
+                ("zbus", input.attrs.zbus),
+                ("heapless", input.attrs.heapless),
+                ("dialoguer", input.attrs.dialoguer),
+                ("rkyv", input.attrs.rkyv),
+                ("sea_orm", input.attrs.sea_orm),
+                ("poem_openapi", input.attrs.poem_openapi),
+                ("profile", !input.attrs.profiles.is_empty()),
+                (
+                    "bool",
+                    input
+                        .variants
+                        .iter()
+                        .any(|variant| variant.attrs.bool.is_some()),
+                ),
+                (
+                    "number",
+                    input
+                        .variants
+                        .iter()
+                        .any(|variant| variant.attrs.number.is_some()),
+                ),
+            ] {
+                if used {
+                    errors.push(Error::new(
+                        call_site,
+                        format(format_args!(
+                            "\"{attribute_name}\" cannot be used with byte string labels"
+                        )),
+                    ));
+                }
+            }
+        }
+
+        // Labels that may not be single characters after transformation are serialized as strings.
+        let char_labels = other.is_none()
+            && input.attrs.qualified.is_none()
+            && input.attrs.serialize_case.is_none()
+            && input
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.char_label);
+
+        if let Some(header_name) = &input.attrs.header_name {
+            if input
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.holds_string())
+            {
+                errors.push(Error::new(
+                    header_name.span(),
+                    "\"header_name\" cannot be used with an \"other\" or \"pattern\" variant",
+                ));
+            }
+            // Labels are encoded with `HeaderValue::from_static`, which panics on anything but
+            // visible ASCII, and aliases outside it can never be decoded.
+            for variant in &input.variants {
+                if let Some(Label::Expr(expr)) = &variant.attrs.string {
+                    errors.push(Error::new(
+                        expr.span(),
+                        "\"header_name\" requires string literal labels",
+                    ));
+                }
+                for label in variant
+                    .attrs
+                    .string
+                    .iter()
+                    .chain(&variant.attrs.aliases)
+                    .filter_map(Label::as_lit)
+                {
+                    if !label
+                        .value()
+                        .bytes()
+                        .all(|b| b == b'\t' || (b' '..=b'~').contains(&b))
+                    {
+                        errors.push(Error::new(
+                            label.span(),
+                            "\"header_name\" requires labels to be visible ASCII",
+                        ));
+                    }
+                }
+            }
+        }
+
+        // D-Bus messages are written through `Serialize`, which must match the string signature.
+        if input.attrs.zbus && input.attrs.serialize_as == SerializeAs::Number {
+            errors.push(Error::new(
+                call_site,
+                "\"zbus\" cannot be used with serialize_as = \"number\"",
+            ));
+        }
+
+        if let Some(remote) = &input.attrs.remote {
+            if input.attrs.serde_as.is_some() || input.attrs.with_module.is_some() {
+                errors.push(Error::new(
+                    remote.span(),
+                    "\"remote\" cannot be used with \"serde_as\" or \"with_module\"",
+                ));
+            }
+            for variant in &input.variants {
+                if variant.attrs.holds_string() || !matches!(variant.fields, Fields::Unit) {
+                    errors.push(Error::new(
+                        variant.ident.span(),
+                        "\"remote\" can only be used with unit variants",
+                    ));
+                }
+            }
+        }
+
+        if input.attrs.prost.is_some() {
+            for variant in &input.variants {
+                if variant.attrs.holds_string() || !matches!(variant.fields, Fields::Unit) {
+                    errors.push(Error::new(
+                        variant.ident.span(),
+                        "\"prost\" can only be used with unit variants",
+                    ));
+                }
+            }
+        }
+
+        if input.attrs.rkyv
+            && input
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.holds_string())
+        {
+            errors.push(Error::new(
+                call_site,
+                "\"rkyv\" cannot be used with an \"other\" or \"pattern\" variant",
+            ));
+        }
+
+        // Active enums must be enumerable.
+        if input.attrs.sea_orm
+            && input
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.holds_string())
+        {
+            errors.push(Error::new(
+                call_site,
+                "\"sea_orm\" cannot be used with an \"other\" or \"pattern\" variant",
+            ));
+        }
+
+        // The schema lists every label.
+        if input.attrs.poem_openapi
+            && input
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.holds_string())
+        {
+            errors.push(Error::new(
                 call_site,
-                "all variants must have \"string\" attribute",
+                "\"poem_openapi\" cannot be used with an \"other\" or \"pattern\" variant",
             ));
         }
 
+        if let Some(uniffi) = input.attrs.uniffi {
+            if !input.generics.params.is_empty() {
+                errors.push(Error::new(
+                    uniffi,
+                    "\"uniffi\" cannot be used with a generic enum",
+                ));
+            }
+            if input
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.holds_string())
+            {
+                errors.push(Error::new(
+                    uniffi,
+                    "\"uniffi\" cannot be used with an \"other\" or \"pattern\" variant",
+                ));
+            }
+        }
+
+        if let Some(c_ffi) = &input.attrs.c_ffi {
+            if !input.generics.params.is_empty() {
+                errors.push(Error::new(
+                    c_ffi.span(),
+                    "\"c_ffi\" cannot be used with a generic enum",
+                ));
+            }
+            for variant in &input.variants {
+                if variant.attrs.holds_string() || !matches!(variant.fields, Fields::Unit) {
+                    errors.push(Error::new(
+                        variant.ident.span(),
+                        "\"c_ffi\" can only be used with unit variants",
+                    ));
+                } else {
+                    match variant.attrs.string.as_ref().and_then(Label::as_lit) {
+                        Some(label) if label.value().contains('\0') => {
+                            errors.push(Error::new(
+                                label.span(),
+                                "\"c_ffi\" labels cannot contain NUL characters",
+                            ));
+                        }
+                        Some(_) => (),
+                        None => {
+                            errors.push(Error::new(
+                                variant.ident.span(),
+                                "\"c_ffi\" requires string literal labels",
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(with_module) = &input.attrs.with_module {
+            if !input.generics.params.is_empty() {
+                errors.push(Error::new(
+                    with_module.span(),
+                    "\"with_module\" cannot be used with a generic enum",
+                ));
+            }
+            if input.attrs.serde_as.is_some() {
+                errors.push(Error::new(
+                    with_module.span(),
+                    "\"with_module\" cannot be used with \"serde_as\"",
+                ));
+            }
+        }
+
+        if let Some(seed) = input.attrs.seed {
+            if !input.generics.params.is_empty() {
+                errors.push(Error::new(
+                    seed,
+                    "\"seed\" cannot be used with a generic enum",
+                ));
+            }
+        }
+
+        if let Some(parser) = input.attrs.parser {
+            if !input.generics.params.is_empty() {
+                errors.push(Error::new(
+                    parser,
+                    "\"parser\" cannot be used with a generic enum",
+                ));
+            }
+        }
+
+        if let Some(generate_tests) = input.attrs.generate_tests {
+            if input.generics.type_params().next().is_some()
+                || input.generics.const_params().next().is_some()
+            {
+                errors.push(Error::new(
+                    generate_tests,
+                    "\"generate_tests\" cannot be used with an enum with type or const parameters",
+                ));
+            }
+        }
+
+        errors.finish()?;
+
         Ok(LabeledStringInput {
             ident: input.ident,
+            vis: input.vis,
+            generics: input.generics,
+            attrs: input.attrs,
             variants: input.variants,
+            byte_labels,
+            char_labels,
         })
     }
 }
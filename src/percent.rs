@@ -0,0 +1,39 @@
+//! Percent-encoding of labels, so that percent-encoded input can be matched without decoding it.
+
+use alloc::{
+    fmt::format,
+    string::String,
+    vec::Vec,
+};
+
+/// Returns the spellings of the given label produced by common percent-encoders, which may write
+/// hexadecimal digits in either case and may encode spaces as `+`, as in query strings.
+///
+/// Spellings equal to the label itself are omitted.
+pub fn encodings(label: &str) -> Vec<String> {
+    let mut encodings = Vec::new();
+    for lowercase in [false, true] {
+        for plus in [false, true] {
+            let encoding = encode(label, lowercase, plus);
+            if encoding != label && !encodings.contains(&encoding) {
+                encodings.push(encoding);
+            }
+        }
+    }
+    encodings
+}
+
+fn encode(label: &str, lowercase: bool, plus: bool) -> String {
+    let mut encoding = String::new();
+    for byte in label.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoding.push(byte as char)
+            }
+            b' ' if plus => encoding.push('+'),
+            _ if lowercase => encoding.push_str(&format(format_args!("%{byte:02x}"))),
+            _ => encoding.push_str(&format(format_args!("%{byte:02X}"))),
+        }
+    }
+    encoding
+}
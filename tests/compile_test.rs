@@ -3,3 +3,10 @@ fn compile_test() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/*.rs");
 }
+
+#[test]
+#[cfg(feature = "headers")]
+fn compile_test_headers() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/headers/*.rs");
+}
@@ -7,6 +7,9 @@ extern crate std;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(all(test, feature = "uniffi"))]
+::uniffi::setup_scaffolding!();
+
 #[cfg(test)]
 mod labeled_strings {
     use core::str::FromStr;
@@ -38,6 +41,48 @@ mod labeled_strings {
         assert_eq!(format(format_args!("{}", Type::Water)), "Water");
     }
 
+    #[test]
+    fn display_honors_formatting_options() {
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        assert_eq!(format(format_args!("{:>6}|", Type::Fire)), "  Fire|");
+        assert_eq!(format(format_args!("{:<6}|", Type::Fire)), "Fire  |");
+        assert_eq!(format(format_args!("{:*^9}", Type::Grass)), "**Grass**");
+        assert_eq!(format(format_args!("{:.3}", Type::Water)), "Wat");
+    }
+
+    #[test]
+    fn matches_labels_and_aliases() {
+        assert!(Type::matches("Grass"));
+        assert!(Type::matches("Hot"));
+        assert!(!Type::matches("Ice"));
+        assert!(!Type::matches(""));
+    }
+
+    #[test]
+    fn derives_as_ref() {
+        fn label<T: AsRef<str>>(value: T) -> usize {
+            value.as_ref().len()
+        }
+
+        assert_eq!(Type::Grass.as_ref(), "Grass");
+        assert_eq!(Type::Fire.as_ref(), "Fire");
+        assert_eq!(label(Type::Water), 5);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn converts_into_borrowed_cow() {
+        extern crate alloc;
+        use alloc::borrow::Cow;
+
+        assert!(matches!(Cow::from(Type::Grass), Cow::Borrowed("Grass")));
+        assert!(matches!(Cow::from(Type::Fire), Cow::Borrowed("Fire")));
+        let label: Cow<'static, str> = Type::Water.into();
+        assert_eq!(label, "Water");
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn derives_to_string() {
@@ -180,142 +225,4319 @@ mod labeled_strings {
     #[test]
     #[cfg(any(feature = "std", feature = "alloc"))]
     fn invalid_value_string() {
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        let err = Type::from_str("bad").unwrap_err();
+        assert_eq!(err.input, "bad");
+        assert_eq!(format(format_args!("{err}")), "invalid Type: bad");
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn invalid_value_converts_to_string() {
         extern crate alloc;
         use alloc::string::String;
 
-        assert_eq!(
-            Type::from_str("bad").err(),
-            Some(String::from("invalid Type: bad"))
-        )
+        fn parse(s: &str) -> Result<Type, String> {
+            Ok(Type::from_str(s)?)
+        }
+
+        assert_eq!(parse("bad"), Err(String::from("invalid Type: bad")));
     }
 
     #[test]
     #[cfg(not(any(feature = "std", feature = "alloc")))]
     fn invalid_value_string() {
-        assert_eq!(Type::from_str("bad").err(), Some("invalid value"))
+        let err = Type::from_str("bad").unwrap_err();
+        assert_eq!(err.input_len, 3);
+        assert_eq!(err.input_prefix(), "bad");
     }
-}
 
-#[cfg(test)]
-mod custom_string_conversion {
-    use core::{
-        fmt::{
-            Display,
-            Formatter,
-        },
-        str::FromStr,
-    };
-
-    use serde_string_enum::{
-        DeserializeStringEnum,
-        SerializeStringEnum,
-    };
+    #[test]
+    #[cfg(not(any(feature = "std", feature = "alloc")))]
+    fn invalid_value_string_truncated() {
+        let err = Type::from_str("a very long invalid value").unwrap_err();
+        assert_eq!(err.input_len, 25);
+        assert_eq!(err.input_prefix(), "a very long inva");
 
-    #[derive(Debug, PartialEq)]
-    enum Rotation {
-        Left,
-        Right,
+        // Truncation never splits a character.
+        let err = Type::from_str("fifteen bytes..é").unwrap_err();
+        assert_eq!(err.input_len, 17);
+        assert_eq!(err.input_prefix(), "fifteen bytes..");
     }
 
-    impl Display for Rotation {
-        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-            match self {
-                Self::Left => write!(f, "L"),
-                Self::Right => write!(f, "R"),
+    #[test]
+    #[cfg(not(any(feature = "std", feature = "alloc")))]
+    fn invalid_value_message() {
+        use core::fmt::Write;
+
+        struct Buffer {
+            bytes: [u8; 32],
+            len: usize,
+        }
+
+        impl Write for Buffer {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                self.bytes[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+                self.len += s.len();
+                Ok(())
             }
         }
+
+        let mut buffer = Buffer {
+            bytes: [0; 32],
+            len: 0,
+        };
+        let err = Type::from_str("bad").unwrap_err();
+        write!(buffer, "{err}").unwrap();
+        assert_eq!(&buffer.bytes[..buffer.len], b"invalid Type");
     }
+}
 
-    impl FromStr for Rotation {
-        type Err = &'static str;
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            Ok(match s {
-                "L" => Self::Left,
-                "R" => Self::Right,
-                _ => return Err("invalid rotation"),
-            })
-        }
+#[cfg(all(test, feature = "unicase"))]
+mod case_folding {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    const METER: &str = "Meter";
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Unit {
+        #[string = "\u{212A}elvin"]
+        Kelvin,
+        #[string = "Straße"]
+        Street,
+        #[string(METER)]
+        Meter,
     }
 
-    #[derive(Debug, PartialEq, SerializeStringEnum, DeserializeStringEnum)]
-    enum Move {
-        Stay,
-        Forward(u8),
-        Rotate(Rotation),
+    #[test]
+    fn matches_folded_labels() {
+        assert_eq!(Unit::from_str("kelvin").unwrap(), Unit::Kelvin);
+        assert_eq!(Unit::from_str("KELVIN").unwrap(), Unit::Kelvin);
+        assert_eq!(Unit::from_str("STRASSE").unwrap(), Unit::Street);
+        assert_eq!(Unit::from_str("straße").unwrap(), Unit::Street);
+        assert_eq!(Unit::from_str("meter").unwrap(), Unit::Meter);
+        assert!(Unit::from_str("kelvins").is_err());
     }
+}
 
-    impl Display for Move {
-        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-            match self {
-                Self::Stay => write!(f, "S"),
-                Self::Forward(n) => write!(f, "F{n}"),
-                Self::Rotate(r) => write!(f, "R{r}"),
-            }
-        }
+#[cfg(test)]
+mod labeled_debug {
+    use serde_string_enum::{
+        DebugLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(DebugLabeledStringEnum, SerializeLabeledStringEnum)]
+    enum Type {
+        #[string = "grass-type"]
+        Grass,
+        #[string = "fire-type"]
+        Fire,
     }
 
-    impl FromStr for Move {
-        type Err = &'static str;
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            Ok(match &s[0..1] {
-                "S" => Self::Stay,
-                "F" => Self::Forward(s[1..].parse::<u8>().map_err(|_| "invalid forward number")?),
-                "R" => Self::Rotate(Rotation::from_str(&s[1..])?),
-                _ => return Err("invalid move"),
-            })
-        }
+    #[derive(DebugLabeledStringEnum)]
+    enum Event<'a> {
+        #[string = "click"]
+        Click,
+        #[string(other)]
+        Other(&'a str),
     }
 
     #[test]
-    fn derives_serialize() {
+    fn derives_debug() {
         extern crate alloc;
-        use alloc::vec;
+        use alloc::fmt::format;
 
-        assert_eq!(serde_json::to_string(&Move::Stay).unwrap(), "\"S\"");
-        assert_eq!(serde_json::to_string(&Move::Forward(1)).unwrap(), "\"F1\"");
+        assert_eq!(format(format_args!("{:?}", Type::Grass)), "\"grass-type\"");
+        assert_eq!(format(format_args!("{:?}", Type::Fire)), "\"fire-type\"");
+        assert_eq!(format(format_args!("{:?}", Event::Click)), "\"click\"");
         assert_eq!(
-            serde_json::to_string(&Move::Rotate(Rotation::Left)).unwrap(),
-            "\"RL\""
+            format(format_args!("{:?}", Event::Other("hover"))),
+            "\"hover\""
         );
+    }
+}
 
-        let moves = vec![
-            Move::Stay,
-            Move::Forward(20),
-            Move::Rotate(Rotation::Right),
-            Move::Forward(10),
-        ];
+#[cfg(test)]
+mod default_variant {
+    use core::{
+        convert::Infallible,
+        str::FromStr,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(default = Unknown)]
+    enum Status {
+        #[string = "Active"]
+        Active,
+        #[string = "Inactive"]
+        Inactive,
+        #[string = "Unknown"]
+        Unknown,
+    }
+
+    #[test]
+    fn from_str_is_infallible() {
+        let result: Result<Status, Infallible> = Status::from_str("Active");
+        assert_eq!(result, Ok(Status::Active));
+        assert_eq!(Status::from_str("Inactive"), Ok(Status::Inactive));
+        assert_eq!(Status::from_str("Unknown"), Ok(Status::Unknown));
+        assert_eq!(Status::from_str("bad"), Ok(Status::Unknown));
+    }
+
+    #[test]
+    fn deserializes_unknown_to_default() {
         assert_eq!(
-            serde_json::to_string(&moves).unwrap(),
-            "[\"S\",\"F20\",\"RR\",\"F10\"]"
+            serde_json::from_str::<Status>("\"Active\"").unwrap(),
+            Status::Active
+        );
+        assert_eq!(
+            serde_json::from_str::<Status>("\"Deleted\"").unwrap(),
+            Status::Unknown
         );
+        assert!(serde_json::from_str::<Status>("1").is_err());
+    }
+}
+
+#[cfg(test)]
+mod unit_error {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(error = "unit")]
+    enum Level {
+        #[string = "Low"]
+        Low,
+        #[string = "High"]
+        High,
     }
 
     #[test]
-    fn derives_deserialize() {
+    fn from_str_returns_unit_error() {
+        assert_eq!(Level::from_str("Low"), Ok(Level::Low));
+        assert_eq!(Level::from_str("High"), Ok(Level::High));
+        assert_eq!(Level::from_str("Medium"), Err(()));
+    }
+
+    #[test]
+    fn deserialize_reports_invalid_value() {
+        assert_eq!(
+            serde_json::from_str::<Level>("\"High\"").unwrap(),
+            Level::High
+        );
+        assert!(serde_json::from_str::<Level>("\"Medium\"").is_err());
+    }
+}
+
+#[cfg(test)]
+mod null_variant {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(null = Missing)]
+    enum Color {
+        #[string = "Red"]
+        Red,
+        #[string = "Blue"]
+        Blue,
+        #[string = "Missing"]
+        Missing,
+    }
+
+    #[test]
+    fn deserializes_null_as_variant() {
         extern crate alloc;
         use alloc::{
             vec,
             vec::Vec,
         };
 
-        assert_eq!(serde_json::from_str::<Move>("\"S\"").unwrap(), Move::Stay);
         assert_eq!(
-            serde_json::from_str::<Move>("\"F123\"").unwrap(),
-            Move::Forward(123)
+            serde_json::from_str::<Color>("null").unwrap(),
+            Color::Missing
         );
         assert_eq!(
-            serde_json::from_str::<Move>("\"RR\"").unwrap(),
-            Move::Rotate(Rotation::Right)
+            serde_json::from_str::<Vec<Color>>("[\"Red\",null]").unwrap(),
+            vec![Color::Red, Color::Missing],
         );
+    }
 
+    #[test]
+    fn deserializes_strings() {
         assert_eq!(
-            serde_json::from_str::<Vec<Move>>("[\"S\",\"F20\",\"RR\",\"F10\"]").unwrap(),
-            vec![
-                Move::Stay,
-                Move::Forward(20),
-                Move::Rotate(Rotation::Right),
-                Move::Forward(10),
-            ],
+            serde_json::from_str::<Color>("\"Red\"").unwrap(),
+            Color::Red
+        );
+        assert_eq!(
+            serde_json::from_str::<Color>("\"Missing\"").unwrap(),
+            Color::Missing
+        );
+        assert_eq!(
+            serde_json::from_str::<Color>("\"Blue\"").unwrap(),
+            Color::Blue
+        );
+        assert!(serde_json::from_str::<Color>("\"Green\"").is_err());
+        assert!(serde_json::from_str::<Color>("1").is_err());
+    }
+}
+
+#[cfg(test)]
+mod bool_variants {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(null = Unset)]
+    enum Toggle {
+        #[string = "On"]
+        #[string(bool = true)]
+        On,
+        #[string = "Off"]
+        #[string(bool = false)]
+        Off,
+        #[string = "Unset"]
+        Unset,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Confirmation {
+        #[string = "Yes"]
+        #[string(bool = true)]
+        Yes,
+        #[string = "Maybe"]
+        Maybe,
+    }
+
+    #[test]
+    fn deserializes_bool() {
+        assert_eq!(serde_json::from_str::<Toggle>("true").unwrap(), Toggle::On);
+        assert_eq!(
+            serde_json::from_str::<Toggle>("false").unwrap(),
+            Toggle::Off
+        );
+        assert_eq!(
+            serde_json::from_str::<Toggle>("\"Off\"").unwrap(),
+            Toggle::Off
+        );
+        assert_eq!(
+            serde_json::from_str::<Toggle>("null").unwrap(),
+            Toggle::Unset
+        );
+        assert!(serde_json::from_str::<Toggle>("1").is_err());
+    }
+
+    #[test]
+    fn rejects_unmapped_bool() {
+        assert_eq!(
+            serde_json::from_str::<Confirmation>("true").unwrap(),
+            Confirmation::Yes
+        );
+        assert!(serde_json::from_str::<Confirmation>("false").is_err());
+    }
+
+    #[test]
+    fn serializes_label() {
+        assert_eq!(serde_json::to_string(&Toggle::On).unwrap(), "\"On\"");
+    }
+}
+
+#[cfg(test)]
+mod number_variants {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        #[number = 1]
+        Grass,
+        #[string = "Fire"]
+        #[number = 2]
+        Fire,
+        #[string = "Water"]
+        Water,
+    }
+
+    #[test]
+    fn deserializes_numbers() {
+        assert_eq!(serde_json::from_str::<Type>("1").unwrap(), Type::Grass);
+        assert_eq!(serde_json::from_str::<Type>("2").unwrap(), Type::Fire);
+        assert!(serde_json::from_str::<Type>("3").is_err());
+        assert!(serde_json::from_str::<Type>("-1").is_err());
+        assert!(serde_json::from_str::<Type>("1.0").is_err());
+    }
+
+    #[test]
+    fn deserializes_strings() {
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Fire\"").unwrap(),
+            Type::Fire
+        );
+        assert!(serde_json::from_str::<Type>("\"2\"").is_err());
+        assert!(Type::from_str("2").is_err());
+    }
+}
+
+#[cfg(test)]
+mod number_serialization {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(serialize_as = "number")]
+    enum Type {
+        #[string = "Grass"]
+        #[number = 1]
+        Grass,
+        #[string = "Fire"]
+        #[number = 2]
+        Fire,
+    }
+
+    #[test]
+    fn serializes_numbers() {
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        assert_eq!(serde_json::to_string(&Type::Grass).unwrap(), "1");
+        assert_eq!(serde_json::to_string(&Type::Fire).unwrap(), "2");
+        assert_eq!(format(format_args!("{}", Type::Fire)), "Fire");
+    }
+
+    #[test]
+    fn deserializes_numbers_and_strings() {
+        assert_eq!(serde_json::from_str::<Type>("2").unwrap(), Type::Fire);
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Fire\"").unwrap(),
+            Type::Fire
+        );
+    }
+}
+
+#[cfg(test)]
+mod serialize_case {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(serialize_case = "lowercase")]
+    enum Region {
+        #[string = "US-East"]
+        #[alias = "Virginia"]
+        UsEast,
+        #[string = "EU-West"]
+        EuWest,
+    }
+
+    #[test]
+    fn serializes_transformed_label() {
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        assert_eq!(
+            serde_json::to_string(&Region::UsEast).unwrap(),
+            "\"us-east\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Region::EuWest).unwrap(),
+            "\"eu-west\""
+        );
+        assert_eq!(format(format_args!("{}", Region::UsEast)), "US-East");
+    }
+
+    #[test]
+    fn deserializes_all_spellings() {
+        assert_eq!(
+            serde_json::from_str::<Region>("\"US-East\"").unwrap(),
+            Region::UsEast
+        );
+        assert_eq!(
+            serde_json::from_str::<Region>("\"us-east\"").unwrap(),
+            Region::UsEast
+        );
+        assert_eq!(
+            serde_json::from_str::<Region>("\"Virginia\"").unwrap(),
+            Region::UsEast
+        );
+        assert_eq!(
+            serde_json::from_str::<Region>("\"eu-west\"").unwrap(),
+            Region::EuWest
+        );
+    }
+}
+
+#[cfg(test)]
+mod label_from_doc {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(label_from_doc)]
+    enum Unit {
+        /// meters
+        ///
+        /// The SI base unit of length.
+        Meter,
+        /// Seconds
+        #[alias = "s"]
+        Second,
+        /// Not used as the label.
+        #[string = "kg"]
+        Kilogram,
+    }
+
+    #[test]
+    fn uses_first_doc_line_as_label() {
+        assert_eq!(serde_json::to_string(&Unit::Meter).unwrap(), "\"meters\"");
+        assert_eq!(serde_json::to_string(&Unit::Second).unwrap(), "\"Seconds\"");
+        assert_eq!(serde_json::to_string(&Unit::Kilogram).unwrap(), "\"kg\"");
+        assert_eq!(Unit::from_str("meters").unwrap(), Unit::Meter);
+        assert_eq!(Unit::from_str("s").unwrap(), Unit::Second);
+    }
+}
+
+#[cfg(test)]
+mod default_fields {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Shape {
+        #[string = "Point"]
+        Point,
+        #[string = "Circle"]
+        #[alias = "Round"]
+        Circle(u32),
+        #[string = "Rectangle"]
+        Rectangle { width: u32, height: u32 },
+    }
+
+    #[test]
+    fn serializes_label_ignoring_fields() {
+        assert_eq!(
+            serde_json::to_string(&Shape::Circle(5)).unwrap(),
+            "\"Circle\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Shape::Rectangle {
+                width: 2,
+                height: 3
+            })
+            .unwrap(),
+            "\"Rectangle\""
+        );
+        assert_eq!(Shape::Circle(5).as_ref(), "Circle");
+    }
+
+    #[test]
+    fn deserializes_default_fields() {
+        assert_eq!(
+            serde_json::from_str::<Shape>("\"Round\"").unwrap(),
+            Shape::Circle(0)
+        );
+        assert_eq!(
+            Shape::from_str("Rectangle"),
+            Ok(Shape::Rectangle {
+                width: 0,
+                height: 0
+            })
+        );
+        assert_eq!(Shape::from_str("Point"), Ok(Shape::Point));
+    }
+
+    #[test]
+    fn converts_indices() {
+        assert_eq!(
+            Shape::Rectangle {
+                width: 2,
+                height: 3
+            }
+            .index(),
+            2
+        );
+        assert_eq!(Shape::from_index(1), Some(Shape::Circle(0)));
+    }
+}
+
+#[cfg(test)]
+mod parse_detailed {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(parse_detailed, default = Unknown)]
+    enum Type {
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        #[alias = "Hot"]
+        Fire,
+        #[string = "Unknown"]
+        Unknown,
+    }
+
+    #[test]
+    fn reports_matched_spelling() {
+        assert_eq!(
+            Type::parse_detailed("Fire"),
+            Ok((Type::Fire, TypeMatchKind::Label))
+        );
+        assert_eq!(
+            Type::parse_detailed("Flame"),
+            Ok((Type::Fire, TypeMatchKind::Alias(0)))
+        );
+        assert_eq!(
+            Type::parse_detailed("Hot"),
+            Ok((Type::Fire, TypeMatchKind::Alias(1)))
+        );
+        assert_eq!(
+            Type::parse_detailed("Water"),
+            Ok((Type::Unknown, TypeMatchKind::Other))
+        );
+    }
+
+    #[cfg(feature = "unicase")]
+    #[test]
+    fn reports_case_folded_spelling() {
+        assert_eq!(
+            Type::parse_detailed("hot"),
+            Ok((Type::Fire, TypeMatchKind::CaseFolded))
+        );
+    }
+}
+
+#[cfg(test)]
+mod const_generics {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Code<const N: usize> {
+        #[string = "Ok"]
+        Ok,
+        #[string = "Err"]
+        #[alias = "Error"]
+        Err,
+    }
+
+    #[test]
+    fn serializes_and_deserializes() {
+        assert_eq!(serde_json::to_string(&Code::<4>::Ok).unwrap(), "\"Ok\"");
+        assert_eq!(
+            serde_json::from_str::<Code<4>>("\"Error\"").unwrap(),
+            Code::Err
+        );
+        assert_eq!(Code::<4>::from_str("Err"), Ok(Code::Err));
+        assert!(Code::<4>::from_str("Fine").is_err());
+    }
+
+    #[cfg(feature = "suggestions")]
+    #[test]
+    fn suggests_closest_label() {
+        use alloc::string::ToString;
+
+        assert_eq!(
+            Code::<4>::from_str("Errr").unwrap_err().to_string(),
+            "invalid Code: Errr, did you mean \"Err\"?"
+        );
+    }
+}
+
+#[cfg(test)]
+mod other_variant {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Event<'a> {
+        #[string = "Click"]
+        #[alias = "Tap"]
+        Click,
+        #[string = "Scroll"]
+        Scroll,
+        #[string(other)]
+        Other(&'a str),
+    }
+
+    #[test]
+    fn serializes_other() {
+        assert_eq!(serde_json::to_string(&Event::Click).unwrap(), "\"Click\"");
+        assert_eq!(
+            serde_json::to_string(&Event::Other("Hover")).unwrap(),
+            "\"Hover\""
+        );
+    }
+
+    #[test]
+    fn as_ref_returns_other_value() {
+        assert_eq!(Event::Scroll.as_ref(), "Scroll");
+        assert_eq!(Event::Other("Hover").as_ref(), "Hover");
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn converts_other_into_owned_cow() {
+        extern crate alloc;
+        use alloc::borrow::Cow;
+
+        assert!(matches!(Cow::from(Event::Click), Cow::Borrowed("Click")));
+        assert!(matches!(Cow::from(Event::Other("Hover")), Cow::Owned(value) if value == "Hover"));
+    }
+
+    #[test]
+    fn deserializes_borrowed_other() {
+        let input = "\"Hover\"";
+        let event = serde_json::from_str::<Event>(input).unwrap();
+        assert_eq!(event, Event::Other("Hover"));
+        match event {
+            Event::Other(value) => assert!(core::ptr::eq(&input[1..6], value)),
+            _ => unreachable!(),
+        }
+
+        assert_eq!(
+            serde_json::from_str::<Event>("\"Tap\"").unwrap(),
+            Event::Click
+        );
+        assert_eq!(
+            serde_json::from_str::<Event>("\"Scroll\"").unwrap(),
+            Event::Scroll
+        );
+    }
+
+    #[test]
+    fn fails_to_borrow_transient_string() {
+        // Escape sequences force the deserializer to produce a transient string.
+        assert!(serde_json::from_str::<Event>("\"Hov\\u0065r\"").is_err());
+        assert_eq!(
+            serde_json::from_str::<Event>("\"Cl\\u0069ck\"").unwrap(),
+            Event::Click
+        );
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    mod owned {
+        extern crate alloc;
+        use alloc::borrow::Cow;
+        use core::{
+            convert::Infallible,
+            str::FromStr,
+        };
+        use serde_string_enum::{
+            DeserializeLabeledStringEnum,
+            SerializeLabeledStringEnum,
+        };
+
+        #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+        enum Event<'a> {
+            #[string = "Click"]
+            Click,
+            #[string(other)]
+            Other(Cow<'a, str>),
+        }
+
+        #[test]
+        fn deserializes_borrowed_or_owned_other() {
+            let event = serde_json::from_str::<Event>("\"Hover\"").unwrap();
+            assert!(matches!(event, Event::Other(Cow::Borrowed("Hover"))));
+
+            let event = serde_json::from_str::<Event>("\"Hov\\u0065r\"").unwrap();
+            assert!(matches!(&event, Event::Other(Cow::Owned(value)) if value == "Hover"));
+        }
+
+        #[test]
+        fn from_str_is_infallible() {
+            let result: Result<Event, Infallible> = Event::from_str("Click");
+            assert_eq!(result, Ok(Event::Click));
+            assert_eq!(
+                Event::from_str("Hover"),
+                Ok(Event::Other(Cow::Borrowed("Hover")))
+            );
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "alloc")))]
+mod pattern_variants {
+    extern crate alloc;
+    use alloc::string::String;
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Code {
+        #[string = "ERR_TIMEOUT"]
+        Timeout,
+        #[string(pattern = "ERR_*")]
+        Error(String),
+        #[string(pattern = "W??N_*")]
+        Warning(String),
+    }
+
+    #[test]
+    fn prefers_labels_over_patterns() {
+        assert_eq!(Code::from_str("ERR_TIMEOUT"), Ok(Code::Timeout));
+    }
+
+    #[test]
+    fn stores_matched_string() {
+        assert_eq!(
+            Code::from_str("ERR_DISK_FULL"),
+            Ok(Code::Error(String::from("ERR_DISK_FULL")))
+        );
+        assert_eq!(
+            Code::from_str("ERR_"),
+            Ok(Code::Error(String::from("ERR_")))
+        );
+        assert_eq!(
+            serde_json::from_str::<Code>("\"WARN_LOW_DISK\"").unwrap(),
+            Code::Warning(String::from("WARN_LOW_DISK"))
+        );
+    }
+
+    #[test]
+    fn fails_without_match() {
+        assert!(Code::from_str("ERR").is_err());
+        assert!(Code::from_str("WRN_LOW_DISK").is_err());
+        assert!(Code::from_str("INFO").is_err());
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Event {
+        #[string(pattern = "user.*")]
+        User(String),
+        #[string(pattern = "user.login.*", priority = 1)]
+        Login(String),
+        #[string(pattern = "*.failed")]
+        Failure(String),
+    }
+
+    #[test]
+    fn tries_higher_priority_first() {
+        assert_eq!(
+            Event::from_str("user.login.ok"),
+            Ok(Event::Login(String::from("user.login.ok")))
+        );
+        assert_eq!(
+            Event::from_str("user.logout"),
+            Ok(Event::User(String::from("user.logout")))
+        );
+        assert_eq!(
+            Event::from_str("user.logout.failed"),
+            Ok(Event::User(String::from("user.logout.failed")))
+        );
+        assert_eq!(
+            Event::from_str("group.failed"),
+            Ok(Event::Failure(String::from("group.failed")))
+        );
+    }
+
+    #[test]
+    fn serializes_matched_string() {
+        assert_eq!(
+            serde_json::to_string(&Code::Error(String::from("ERR_DISK_FULL"))).unwrap(),
+            "\"ERR_DISK_FULL\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Code::Timeout).unwrap(),
+            "\"ERR_TIMEOUT\""
+        );
+    }
+
+    #[cfg(feature = "unicase")]
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Address {
+        #[string = "Weg"]
+        Way,
+        #[string(pattern = "*straße ?")]
+        Street(String),
+    }
+
+    #[cfg(feature = "unicase")]
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(case_fold = "simple")]
+    enum SimpleAddress {
+        #[string = "Weg"]
+        Way,
+        #[string(pattern = "*straße ?")]
+        Street(String),
+    }
+
+    #[cfg(feature = "unicase")]
+    #[test]
+    fn folds_case_like_labels() {
+        assert_eq!(
+            Address::from_str("HAUPTSTRASSE 5"),
+            Ok(Address::Street(String::from("HAUPTSTRASSE 5")))
+        );
+        assert_eq!(
+            Address::from_str("Hauptstraße 5"),
+            Ok(Address::Street(String::from("Hauptstraße 5")))
+        );
+        assert!(Address::from_str("HAUPTSTRASSE").is_err());
+        assert_eq!(
+            SimpleAddress::from_str("HAUPTSTRAẞE 5"),
+            Ok(SimpleAddress::Street(String::from("HAUPTSTRAẞE 5")))
+        );
+        assert!(SimpleAddress::from_str("HAUPTSTRASSE 5").is_err());
+    }
+}
+
+#[cfg(test)]
+mod label_policy {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(label_policy = "kebab")]
+    enum Mode {
+        #[string = "dry-run"]
+        #[alias = "dry"]
+        DryRun,
+        #[string = "apply-v2"]
+        Apply,
+    }
+
+    #[test]
+    fn accepts_conforming_labels() {
+        assert_eq!(serde_json::to_string(&Mode::DryRun).unwrap(), "\"dry-run\"");
+        assert_eq!(Mode::from_str("apply-v2").unwrap(), Mode::Apply);
+    }
+}
+
+#[cfg(test)]
+mod qualified {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(qualified)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    const WATER: &str = "water";
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(qualified = ".", serialize_case = "lowercase")]
+    enum Element {
+        #[string = "Earth"]
+        Earth,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(qualified = "/")]
+    enum Liquid {
+        #[string(WATER)]
+        Water,
+    }
+
+    #[test]
+    fn serializes_with_enum_name() {
+        assert_eq!(
+            serde_json::to_string(&Type::Grass).unwrap(),
+            "\"Type::Grass\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Element::Earth).unwrap(),
+            "\"Element.earth\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Liquid::Water).unwrap(),
+            "\"Liquid/water\""
+        );
+    }
+
+    #[test]
+    fn deserializes_with_or_without_qualifier() {
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Type::Fire\"").unwrap(),
+            Type::Fire
+        );
+        assert_eq!(Type::from_str("Fire").unwrap(), Type::Fire);
+        assert_eq!(Element::from_str("Element.earth").unwrap(), Element::Earth);
+        assert_eq!(Liquid::from_str("Liquid/water").unwrap(), Liquid::Water);
+        assert!(Type::from_str("Element::Fire").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "percent_decode"))]
+mod percent_decode {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(percent_decode)]
+    enum Region {
+        #[string = "North America"]
+        NorthAmerica,
+        #[string = "Zürich"]
+        #[alias = "Zurich/Airport"]
+        Zurich,
+    }
+
+    #[test]
+    fn matches_percent_encoded_labels() {
+        assert_eq!(
+            Region::from_str("North%20America").unwrap(),
+            Region::NorthAmerica
+        );
+        assert_eq!(
+            Region::from_str("North+America").unwrap(),
+            Region::NorthAmerica
+        );
+        assert_eq!(Region::from_str("Z%C3%BCrich").unwrap(), Region::Zurich);
+        assert_eq!(Region::from_str("Z%c3%bcrich").unwrap(), Region::Zurich);
+        assert_eq!(
+            Region::from_str("Zurich%2FAirport").unwrap(),
+            Region::Zurich
+        );
+        assert_eq!(
+            serde_json::to_string(&Region::NorthAmerica).unwrap(),
+            "\"North America\""
+        );
+    }
+}
+
+#[cfg(all(test, feature = "lookup_table"))]
+mod lookup_table {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    const DRAGON: &str = "Dragon";
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Plant"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+        #[string(DRAGON)]
+        Dragon,
+        #[string = "Ünknown"]
+        Unknown,
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Event<'a> {
+        #[string = "Click"]
+        Click,
+        #[string(other)]
+        Other(&'a str),
+    }
+
+    #[test]
+    fn looks_up_labels() {
+        assert_eq!(Type::from_str("Grass").unwrap(), Type::Grass);
+        assert_eq!(Type::from_str("Plant").unwrap(), Type::Grass);
+        assert_eq!(Type::from_str("Fire").unwrap(), Type::Fire);
+        assert_eq!(Type::from_str("Dragon").unwrap(), Type::Dragon);
+        assert_eq!(Type::from_str("Ünknown").unwrap(), Type::Unknown);
+        assert!(Type::from_str("Water").is_err());
+        assert_eq!(
+            serde_json::from_str::<Event>("\"Click\"").unwrap(),
+            Event::Click
+        );
+        assert_eq!(
+            serde_json::from_str::<Event>("\"Scroll\"").unwrap(),
+            Event::Other("Scroll")
+        );
+    }
+
+    #[cfg(feature = "unicase")]
+    #[test]
+    fn looks_up_folded_labels() {
+        assert_eq!(Type::from_str("gRASS").unwrap(), Type::Grass);
+        assert_eq!(Type::from_str("PLANT").unwrap(), Type::Grass);
+        assert_eq!(Type::from_str("dragon").unwrap(), Type::Dragon);
+        assert_eq!(Type::from_str("üNKNOWN").unwrap(), Type::Unknown);
+    }
+}
+
+#[cfg(all(test, feature = "runtime"))]
+mod runtime {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+    use serde_string_enum_runtime::label::FromLabel;
+
+    const DRAGON: &str = "Dragon";
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Plant"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+        #[string(DRAGON)]
+        Dragon,
+    }
+
+    #[test]
+    fn parses_through_runtime() {
+        assert_eq!(Type::NAME, "Type");
+        assert_eq!(Type::from_label("Plant"), Some(Type::Grass));
+        assert_eq!(Type::from_str("Grass").unwrap(), Type::Grass);
+        assert_eq!(Type::from_str("Dragon").unwrap(), Type::Dragon);
+        assert!(Type::from_str("Water").is_err());
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Fire\"").unwrap(),
+            Type::Fire
+        );
+        assert!(serde_json::from_str::<Type>("\"Water\"").is_err());
+    }
+
+    #[cfg(feature = "unicase")]
+    #[test]
+    fn parses_case_insensitively_through_runtime() {
+        assert_eq!(Type::from_str("GRASS").unwrap(), Type::Grass);
+        assert_eq!(Type::from_str("dragon").unwrap(), Type::Dragon);
+        assert_eq!(Type::from_label("pLANT"), Some(Type::Grass));
+    }
+
+    #[test]
+    fn describes_labels() {
+        use serde_string_enum_runtime::StringEnum;
+
+        let variant: &dyn StringEnum = &Type::Grass;
+        assert_eq!(variant.label(), "Grass");
+        assert_eq!(variant.aliases(), ["Plant"]);
+        assert_eq!(Type::Dragon.label(), "Dragon");
+        assert!(Type::Fire.aliases().is_empty());
+        assert_eq!(<Type as StringEnum>::from_label("Fire"), Some(Type::Fire));
+        assert_eq!(
+            <Type as StringEnum>::variants(),
+            [Type::Grass, Type::Fire, Type::Dragon]
+        );
+    }
+
+    #[test]
+    fn describes_enum() {
+        use serde_string_enum_runtime::VariantMeta;
+
+        assert_eq!(Type::META.name, "Type");
+        assert_eq!(Type::META.case_sensitive, cfg!(not(feature = "unicase")));
+        assert_eq!(
+            Type::META.variants[0],
+            VariantMeta {
+                name: "Grass",
+                label: Some("Grass"),
+                aliases: &["Plant"],
+            }
+        );
+        assert_eq!(Type::META.variants[2].label, Some("Dragon"));
+    }
+
+    #[test]
+    fn registers_enums() {
+        use serde_string_enum_runtime::registry;
+
+        let variants = registry::register::<Type>();
+        let type_name = core::any::type_name::<Type>();
+        assert!(registry::type_names().contains(&type_name));
+        assert_eq!(registry::get(type_name).unwrap().len(), 3);
+        assert_eq!(variants[1].label(), "Fire");
+        assert!(registry::get("Unregistered").is_none());
+    }
+}
+
+#[cfg(all(test, feature = "alias_registry"))]
+mod alias_registry {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[test]
+    fn accepts_registered_aliases() {
+        assert!(Type::from_str("Blaze").is_err());
+        assert!(!Type::matches("Blaze"));
+        Type::register_alias("Blaze", Type::Fire);
+        assert_eq!(Type::from_str("Blaze"), Ok(Type::Fire));
+        assert!(Type::matches("Blaze"));
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Blaze\"").unwrap(),
+            Type::Fire
+        );
+    }
+
+    #[test]
+    fn prefers_labels() {
+        Type::register_alias("Grass", Type::Fire);
+        assert_eq!(Type::from_str("Grass"), Ok(Type::Grass));
+    }
+}
+
+#[cfg(test)]
+mod large_enums {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    macro_rules! large_enum {
+        ($($variant:ident)*) => {
+            #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+            enum Large {
+                $(
+                    #[string = stringify!($variant)]
+                    $variant,
+                )*
+            }
+        };
+    }
+
+    large_enum! {
+        V0 V1 V2 V3 V4 V5 V6 V7 V8 V9 V10 V11 V12 V13 V14
+        V15 V16 V17 V18 V19 V20 V21 V22 V23 V24 V25 V26 V27 V28 V29
+        V30 V31 V32 V33 V34 V35 V36 V37 V38 V39 V40 V41 V42 V43 V44
+        V45 V46 V47 V48 V49 V50 V51 V52 V53 V54 V55 V56 V57 V58 V59
+        V60 V61 V62 V63 V64 V65 V66 V67 V68 V69 V70 V71 V72 V73 V74
+        V75 V76 V77 V78 V79 V80 V81 V82 V83 V84 V85 V86 V87 V88 V89
+        V90 V91 V92 V93 V94 V95 V96 V97 V98 V99 V100 V101 V102 V103 V104
+        V105 V106 V107 V108 V109 V110 V111 V112 V113 V114 V115 V116 V117 V118 V119
+        V120 V121 V122 V123 V124 V125 V126 V127 V128 V129 V130 V131 V132 V133 V134
+        V135 V136 V137 V138 V139 V140 V141 V142 V143 V144 V145 V146 V147 V148 V149
+    }
+
+    #[test]
+    fn splits_large_enums_into_chunks() {
+        assert_eq!(Large::from_str("V0").unwrap(), Large::V0);
+        assert_eq!(Large::from_str("V64").unwrap(), Large::V64);
+        assert_eq!(Large::from_str("V149").unwrap(), Large::V149);
+        assert!(Large::from_str("V150").is_err());
+        assert_eq!(serde_json::to_string(&Large::V100).unwrap(), "\"V100\"");
+        assert_eq!(
+            serde_json::from_str::<Large>("\"V127\"").unwrap(),
+            Large::V127
+        );
+    }
+}
+
+#[cfg(test)]
+mod inline {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(inline)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[test]
+    fn inlines_generated_functions() {
+        assert_eq!(Type::from_str("Grass").unwrap(), Type::Grass);
+        assert!(Type::from_str("Water").is_err());
+        assert_eq!(serde_json::to_string(&Type::Fire).unwrap(), "\"Fire\"");
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Fire\"").unwrap(),
+            Type::Fire
+        );
+    }
+}
+
+#[cfg(test)]
+mod seed {
+    use serde::de::DeserializeSeed;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(seed)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Plant"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[test]
+    fn matches_with_runtime_options() {
+        let exact = TypeSeed {
+            case_insensitive: false,
+            allow_aliases: false,
+        };
+        assert_eq!(exact.from_label("Grass"), Some(Type::Grass));
+        assert_eq!(exact.from_label("Plant"), None);
+        assert_eq!(exact.from_label("fire"), None);
+
+        let relaxed = TypeSeed {
+            case_insensitive: true,
+            allow_aliases: true,
+        };
+        assert_eq!(relaxed.from_label("plant"), Some(Type::Grass));
+        assert_eq!(relaxed.from_label("FIRE"), Some(Type::Fire));
+
+        let mut deserializer = serde_json::Deserializer::from_str("\"fIRE\"");
+        assert_eq!(relaxed.deserialize(&mut deserializer).unwrap(), Type::Fire);
+        let mut deserializer = serde_json::Deserializer::from_str("\"Plant\"");
+        assert!(exact.deserialize(&mut deserializer).is_err());
+        assert_eq!(TypeSeed::default().from_label("Plant"), Some(Type::Grass));
+    }
+}
+
+#[cfg(test)]
+mod parser {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(parser)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Plant"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(parser, default = Unknown)]
+    enum Status {
+        #[string = "Active"]
+        #[alias = "On"]
+        Active,
+        #[string = "Unknown"]
+        Unknown,
+    }
+
+    #[test]
+    fn matches_with_builder_options() {
+        let strict = TypeParser::new().case_insensitive(false).aliases(false);
+        assert_eq!(strict.parse("Grass"), Ok(Type::Grass));
+        assert!(strict.parse("Plant").is_err());
+        assert!(strict.parse("fire").is_err());
+
+        let lenient = TypeParser::new().case_insensitive(true).aliases(true);
+        assert_eq!(lenient.parse("plant"), Ok(Type::Grass));
+        assert_eq!(lenient.parse("FIRE"), Ok(Type::Fire));
+        assert_eq!(TypeParser::default().parse("Plant"), Ok(Type::Grass));
+    }
+
+    #[test]
+    fn fails_like_from_str() {
+        let strict = TypeParser::new().aliases(false);
+        assert_eq!(
+            strict.parse("Water").unwrap_err(),
+            "Water".parse::<Type>().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn falls_back_like_from_str() {
+        let strict = StatusParser::new().aliases(false);
+        assert_eq!(strict.parse("On"), Ok(Status::Unknown));
+        assert_eq!(StatusParser::new().parse("On"), Ok(Status::Active));
+    }
+}
+
+#[cfg(test)]
+mod public_visitor {
+    use serde::{
+        Deserialize,
+        Deserializer,
+    };
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(public_visitor)]
+    pub enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Primary(Type);
+
+    impl<'de> Deserialize<'de> for Primary {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer
+                .deserialize_str(TypeVisitor::new())
+                .map(Primary)
+        }
+    }
+
+    #[test]
+    fn delegates_to_visitor() {
+        assert_eq!(
+            serde_json::from_str::<Primary>("\"Fire\"").unwrap(),
+            Primary(Type::Fire)
+        );
+        assert!(serde_json::from_str::<Primary>("\"Water\"").is_err());
+    }
+}
+
+#[cfg(test)]
+mod byte_labels {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Opcode {
+        #[string = b"\x01GR"]
+        #[alias = b"\x01gr"]
+        Grass,
+        #[string = b"\xffFI"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(default = Unknown)]
+    enum Flag {
+        #[string = b"Y"]
+        Yes,
+        #[string = b"?"]
+        Unknown,
+    }
+
+    #[test]
+    fn returns_label_bytes() {
+        const GRASS: &[u8] = Opcode::Grass.label_bytes();
+        assert_eq!(GRASS, b"\x01GR");
+        assert_eq!(Opcode::Fire.label_bytes(), b"\xffFI");
+    }
+
+    #[test]
+    fn serializes_bytes() {
+        assert_eq!(serde_json::to_string(&Opcode::Grass).unwrap(), "[1,71,82]");
+        assert_eq!(serde_json::to_string(&Opcode::Fire).unwrap(), "[255,70,73]");
+    }
+
+    #[test]
+    fn deserializes_bytes() {
+        assert_eq!(
+            serde_json::from_str::<Opcode>("[1,71,82]").unwrap(),
+            Opcode::Grass
+        );
+        assert_eq!(
+            serde_json::from_str::<Opcode>("[1,103,114]").unwrap(),
+            Opcode::Grass
+        );
+        assert_eq!(
+            serde_json::from_str::<Opcode>("[255,70,73]").unwrap(),
+            Opcode::Fire
+        );
+        assert!(serde_json::from_str::<Opcode>("[255,70,73,0]").is_err());
+        assert!(serde_json::from_str::<Opcode>("[]").is_err());
+        assert_eq!(serde_json::from_str::<Flag>("\"Y\"").unwrap(), Flag::Yes);
+        assert_eq!(
+            serde_json::from_str::<Flag>("[78,79]").unwrap(),
+            Flag::Unknown
+        );
+    }
+}
+
+#[cfg(test)]
+mod char_labels {
+    use core::str::FromStr;
+    use serde::{
+        ser::Impossible,
+        Serialize,
+        Serializer,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = 'G']
+        #[alias = "Grass"]
+        Grass,
+        #[string('F')]
+        Fire,
+    }
+
+    /// Serializes only characters, to check which method is called.
+    struct CharSerializer;
+
+    macro_rules! unsupported {
+        ($($method:ident($($ty:ty),*)),* $(,)?) => {
+            $(
+                fn $method(self, $(_: $ty),*) -> Result<Self::Ok, Self::Error> {
+                    Err(core::fmt::Error)
+                }
+            )*
+        };
+    }
+
+    impl Serializer for CharSerializer {
+        type Ok = char;
+        type Error = core::fmt::Error;
+        type SerializeSeq = Impossible<char, core::fmt::Error>;
+        type SerializeTuple = Impossible<char, core::fmt::Error>;
+        type SerializeTupleStruct = Impossible<char, core::fmt::Error>;
+        type SerializeTupleVariant = Impossible<char, core::fmt::Error>;
+        type SerializeMap = Impossible<char, core::fmt::Error>;
+        type SerializeStruct = Impossible<char, core::fmt::Error>;
+        type SerializeStructVariant = Impossible<char, core::fmt::Error>;
+
+        fn serialize_char(self, v: char) -> Result<char, core::fmt::Error> {
+            Ok(v)
+        }
+
+        unsupported! {
+            serialize_bool(bool),
+            serialize_i8(i8),
+            serialize_i16(i16),
+            serialize_i32(i32),
+            serialize_i64(i64),
+            serialize_u8(u8),
+            serialize_u16(u16),
+            serialize_u32(u32),
+            serialize_u64(u64),
+            serialize_f32(f32),
+            serialize_f64(f64),
+            serialize_str(&str),
+            serialize_bytes(&[u8]),
+            serialize_none(),
+            serialize_unit(),
+            serialize_unit_struct(&'static str),
+            serialize_unit_variant(&'static str, u32, &'static str),
+        }
+
+        fn serialize_some<T>(self, _: &T) -> Result<char, core::fmt::Error>
+        where
+            T: ?Sized + Serialize,
+        {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_newtype_struct<T>(
+            self,
+            _: &'static str,
+            _: &T,
+        ) -> Result<char, core::fmt::Error>
+        where
+            T: ?Sized + Serialize,
+        {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_newtype_variant<T>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<char, core::fmt::Error>
+        where
+            T: ?Sized + Serialize,
+        {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, core::fmt::Error> {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, core::fmt::Error> {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleStruct, core::fmt::Error> {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, core::fmt::Error> {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, core::fmt::Error> {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStruct, core::fmt::Error> {
+            Err(core::fmt::Error)
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, core::fmt::Error> {
+            Err(core::fmt::Error)
+        }
+
+        fn collect_str<T>(self, _: &T) -> Result<char, core::fmt::Error>
+        where
+            T: ?Sized + core::fmt::Display,
+        {
+            Err(core::fmt::Error)
+        }
+    }
+
+    #[test]
+    fn serializes_chars() {
+        assert_eq!(Type::Grass.serialize(CharSerializer), Ok('G'));
+        assert_eq!(Type::Fire.serialize(CharSerializer), Ok('F'));
+        assert_eq!(serde_json::to_string(&Type::Fire).unwrap(), "\"F\"");
+    }
+
+    #[test]
+    fn deserializes_chars() {
+        assert_eq!(serde_json::from_str::<Type>("\"G\"").unwrap(), Type::Grass);
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Grass\"").unwrap(),
+            Type::Grass
+        );
+        assert_eq!(Type::from_str("F").unwrap(), Type::Fire);
+        assert!(Type::from_str("W").is_err());
+    }
+}
+
+#[cfg(test)]
+mod description {
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(SerializeLabeledStringEnum)]
+    #[string_enum(description)]
+    enum Type<'a> {
+        /// Strong against water.
+        #[string = "Grass"]
+        Grass,
+        /// Strong against grass.
+        ///
+        /// Weak against water.
+        #[string = "Fire"]
+        Fire,
+        #[string = "Water"]
+        Water,
+        /// A type from a later generation.
+        #[string(other)]
+        Other(&'a str),
+    }
+
+    #[test]
+    fn returns_doc_comments() {
+        assert_eq!(Type::Grass.description(), "Strong against water.");
+        assert_eq!(
+            Type::Fire.description(),
+            "Strong against grass.\n\nWeak against water."
+        );
+        assert_eq!(Type::Water.description(), "");
+        assert_eq!(
+            Type::Other("Fairy").description(),
+            "A type from a later generation."
+        );
+    }
+}
+
+#[cfg(all(test, feature = "suggestions"))]
+mod suggestions {
+    extern crate alloc;
+
+    use alloc::string::ToString;
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+        #[string = "Water"]
+        Water,
+    }
+
+    #[test]
+    fn suggests_closest_label() {
+        assert_eq!(
+            Type::from_str("Fier").unwrap_err().to_string(),
+            "invalid Type: Fier, did you mean \"Fire\"?"
+        );
+        assert_eq!(
+            Type::from_str("flam").unwrap_err().to_string(),
+            "invalid Type: flam, did you mean \"Flame\"?"
+        );
+        assert_eq!(
+            Type::from_str("Electric").unwrap_err().to_string(),
+            "invalid Type: Electric"
+        );
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Watr\"")
+                .unwrap_err()
+                .to_string(),
+            "unknown Type \"Watr\", did you mean \"Water\"? at line 1 column 6"
+        );
+    }
+
+    #[test]
+    fn finds_closest_match() {
+        assert_eq!(Type::closest_match("Grass"), Some(Type::Grass));
+        assert_eq!(Type::closest_match("Fier"), Some(Type::Fire));
+        assert_eq!(Type::closest_match("flam"), Some(Type::Fire));
+        assert_eq!(Type::closest_match("Electric"), None);
+    }
+}
+
+#[cfg(test)]
+mod completions {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(DeserializeLabeledStringEnum)]
+    #[string_enum(completions)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Green"]
+        Grass,
+        #[string = "Ground"]
+        Ground,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[derive(DeserializeLabeledStringEnum)]
+    #[string_enum(completions = "aliases")]
+    enum Command {
+        #[string = "dry-run"]
+        #[alias = "dry"]
+        DryRun,
+        #[string = "run"]
+        Run,
+    }
+
+    #[test]
+    fn completes_labels() {
+        let mut completions = Type::completions("Gr");
+        assert_eq!(completions.next(), Some("Grass"));
+        assert_eq!(completions.next(), Some("Ground"));
+        assert_eq!(completions.next(), None);
+        assert_eq!(Type::completions("").count(), 3);
+        assert_eq!(Type::completions("W").next(), None);
+    }
+
+    #[test]
+    fn completes_aliases() {
+        let mut completions = Command::completions("dr");
+        assert_eq!(completions.next(), Some("dry-run"));
+        assert_eq!(completions.next(), Some("dry"));
+        assert_eq!(completions.next(), None);
+    }
+
+    #[cfg(feature = "unicase")]
+    #[test]
+    fn ignores_case() {
+        assert_eq!(Type::completions("fi").next(), Some("Fire"));
+    }
+}
+
+#[cfg(test)]
+mod parse_prefix {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(parse_prefix)]
+    enum Operator {
+        #[string = "<"]
+        Less,
+        #[string = "<="]
+        LessEqual,
+        #[string = "="]
+        #[alias = "=="]
+        Equal,
+    }
+
+    #[test]
+    fn parses_longest_label() {
+        assert_eq!(
+            Operator::parse_prefix("<= 3"),
+            Some((Operator::LessEqual, " 3"))
+        );
+        assert_eq!(Operator::parse_prefix("< 3"), Some((Operator::Less, " 3")));
+        assert_eq!(Operator::parse_prefix("==x"), Some((Operator::Equal, "x")));
+        assert_eq!(Operator::parse_prefix("="), Some((Operator::Equal, "")));
+    }
+
+    #[test]
+    fn fails_without_label() {
+        assert_eq!(Operator::parse_prefix("> 3"), None);
+        assert_eq!(Operator::parse_prefix(""), None);
+    }
+}
+
+#[cfg(test)]
+mod overlapping_labels {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Plant"]
+        Grass,
+        #[string = "Bug"]
+        #[alias = "Plant"]
+        Bug,
+    }
+
+    #[test]
+    fn matches_first_variant() {
+        assert_eq!(Type::from_str("Plant"), Ok(Type::Grass));
+        assert_eq!(Type::from_str("Bug"), Ok(Type::Bug));
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "alloc")))]
+mod list_delimiter {
+    extern crate alloc;
+
+    use alloc::{
+        string::ToString,
+        vec,
+        vec::Vec,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(list_delimiter = ",")]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Leaf"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+        #[string = "Water"]
+        Water,
+    }
+
+    #[test]
+    fn parses_list() {
+        assert_eq!(
+            Type::parse_list("Grass,Fire, Leaf "),
+            Ok(vec![Type::Grass, Type::Fire, Type::Grass])
+        );
+        assert_eq!(Type::parse_list(""), Ok(Vec::new()));
+        assert_eq!(
+            Type::parse_list("Grass,Electric").unwrap_err().to_string(),
+            "invalid Type: Electric"
+        );
+    }
+
+    #[test]
+    fn formats_list() {
+        assert_eq!(
+            Type::format_list(&[Type::Water, Type::Grass]),
+            "Water,Grass"
+        );
+        assert_eq!(Type::format_list(&[]), "");
+        let list = Type::format_list(&[Type::Fire, Type::Water]);
+        assert_eq!(Type::parse_list(&list), Ok(vec![Type::Fire, Type::Water]));
+    }
+}
+
+#[cfg(test)]
+mod catch_all_with {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(catch_all_with = trimmed)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    fn trimmed(s: &str) -> Option<Type> {
+        let trimmed = s.trim();
+        if trimmed.len() == s.len() {
+            return None;
+        }
+        Type::from_str(trimmed).ok()
+    }
+
+    #[test]
+    fn resolves_unknown_strings() {
+        assert_eq!(Type::from_str(" Grass "), Ok(Type::Grass));
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Fire\\n\"").unwrap(),
+            Type::Fire
+        );
+        assert!(Type::from_str(" Water ").is_err());
+    }
+}
+
+#[cfg(test)]
+mod on_unknown {
+    use core::{
+        str::FromStr,
+        sync::atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+    };
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    static UNKNOWN: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_unknown(_: &str) {
+        UNKNOWN.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(default = Unknown, on_unknown = count_unknown)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Unknown"]
+        Unknown,
+    }
+
+    #[test]
+    fn observes_unknown_strings() {
+        assert_eq!(Type::from_str("Grass"), Ok(Type::Grass));
+        assert_eq!(UNKNOWN.load(Ordering::Relaxed), 0);
+        assert_eq!(Type::from_str("Water"), Ok(Type::Unknown));
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Fire\"").unwrap(),
+            Type::Unknown
+        );
+        assert_eq!(UNKNOWN.load(Ordering::Relaxed), 2);
+        assert!(!Type::matches("Ice"));
+        assert_eq!(UNKNOWN.load(Ordering::Relaxed), 2);
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tracing_fallback {
+    use core::{
+        str::FromStr,
+        sync::atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+    };
+    use serde_string_enum::DeserializeLabeledStringEnum;
+    use tracing::{
+        span,
+        subscriber,
+        Event,
+        Level,
+        Metadata,
+        Subscriber,
+    };
+
+    static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountWarnings;
+
+    impl Subscriber for CountWarnings {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            if *event.metadata().level() == Level::WARN {
+                WARNINGS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(default = Unknown)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Unknown"]
+        Unknown,
+    }
+
+    #[test]
+    fn warns_on_fallback() {
+        subscriber::with_default(CountWarnings, || {
+            assert_eq!(Type::from_str("Grass"), Ok(Type::Grass));
+            assert_eq!(WARNINGS.load(Ordering::Relaxed), 0);
+            assert_eq!(Type::from_str("Water"), Ok(Type::Unknown));
+            assert_eq!(
+                serde_json::from_str::<Type>("\"Fire\"").unwrap(),
+                Type::Unknown
+            );
+            assert_eq!(WARNINGS.load(Ordering::Relaxed), 2);
+        });
+    }
+}
+
+#[cfg(test)]
+mod alias_switch {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(alias_switch)]
+    enum Type {
+        #[string = "Electric"]
+        #[alias = "Lightning"]
+        Electric,
+        #[string = "Grass"]
+        Grass,
+    }
+
+    #[test]
+    fn serializes_requested_alias() {
+        assert_eq!(
+            Type::Electric
+                .serialize_as_alias(0, serde_json::value::Serializer)
+                .unwrap(),
+            serde_json::json!("Lightning")
+        );
+        assert_eq!(
+            Type::Grass
+                .serialize_as_alias(0, serde_json::value::Serializer)
+                .unwrap(),
+            serde_json::json!("Grass")
+        );
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum)]
+    #[string_enum(alias_switch, qualified, serialize_case = "lowercase")]
+    enum QualifiedType {
+        #[string = "Electric"]
+        #[alias = "Lightning"]
+        Electric,
+    }
+
+    #[test]
+    fn serializes_labels_by_default() {
+        assert_eq!(
+            serde_json::to_string(&Type::Electric).unwrap(),
+            "\"Electric\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Lightning\"").unwrap(),
+            Type::Electric
+        );
+    }
+
+    #[test]
+    fn serializes_alias_like_label() {
+        assert_eq!(
+            serde_json::to_string(&QualifiedType::Electric).unwrap(),
+            "\"QualifiedType::electric\""
+        );
+        assert_eq!(
+            QualifiedType::Electric
+                .serialize_as_alias(0, serde_json::value::Serializer)
+                .unwrap(),
+            serde_json::json!("QualifiedType::lightning")
+        );
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod profile {
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum)]
+    #[string_enum(profile(v1 = 0, v0 = 1))]
+    enum Type {
+        #[string = "Electric"]
+        #[alias = "Lightning"]
+        #[alias = "Thunder"]
+        Electric,
+        #[string = "Grass"]
+        Grass,
+    }
+
+    #[test]
+    fn serializes_with_profile() {
+        assert_eq!(
+            Type::with_profile("v1", || serde_json::to_string(&Type::Electric).unwrap()),
+            "\"Lightning\""
+        );
+        assert_eq!(
+            Type::with_profile("v0", || serde_json::to_string(&Type::Electric).unwrap()),
+            "\"Thunder\""
+        );
+        assert_eq!(
+            Type::with_profile("v1", || serde_json::to_string(&Type::Grass).unwrap()),
+            "\"Grass\""
+        );
+        assert_eq!(
+            Type::with_profile("v2", || serde_json::to_string(&Type::Electric).unwrap()),
+            "\"Electric\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Type::Electric).unwrap(),
+            "\"Electric\""
+        );
+    }
+
+    #[test]
+    fn restores_previous_profile() {
+        Type::with_profile("v1", || {
+            Type::with_profile("v0", || {
+                assert_eq!(
+                    serde_json::to_string(&Type::Electric).unwrap(),
+                    "\"Thunder\""
+                );
+            });
+            assert_eq!(
+                serde_json::to_string(&Type::Electric).unwrap(),
+                "\"Lightning\""
+            );
+        });
+        assert_eq!(
+            serde_json::to_string(&Type::Electric).unwrap(),
+            "\"Electric\""
+        );
+    }
+
+    #[test]
+    fn serializes_with_explicit_profile() {
+        assert_eq!(
+            Type::Electric
+                .serialize_with_profile("v0", serde_json::value::Serializer)
+                .unwrap(),
+            serde_json::json!("Thunder")
+        );
+        assert_eq!(
+            Type::Electric
+                .serialize_with_profile("v2", serde_json::value::Serializer)
+                .unwrap(),
+            serde_json::json!("Electric")
+        );
+    }
+
+    #[test]
+    fn scopes_profile_to_current_thread() {
+        Type::with_profile("v1", || {
+            let other = std::thread::spawn(|| serde_json::to_string(&Type::Electric).unwrap());
+            assert_eq!(other.join().unwrap(), "\"Electric\"");
+        });
+    }
+}
+
+#[cfg(test)]
+mod allow_empty {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(allow_empty)]
+    enum Suffix {
+        #[string = ""]
+        None,
+        #[string = "Jr."]
+        Junior,
+    }
+
+    #[test]
+    fn parses_empty_label() {
+        assert_eq!(Suffix::from_str(""), Ok(Suffix::None));
+        assert_eq!(Suffix::from_str("Jr."), Ok(Suffix::Junior));
+        assert_eq!(serde_json::to_string(&Suffix::None).unwrap(), "\"\"");
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod borrow_str {
+    extern crate std;
+
+    use core::hash::{
+        Hash,
+        Hasher,
+    };
+    use serde_string_enum::SerializeLabeledStringEnum;
+    use std::collections::HashSet;
+
+    #[derive(Debug, SerializeLabeledStringEnum)]
+    #[string_enum(borrow_str)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+        #[string = "Water"]
+        Water,
+    }
+
+    impl PartialEq for Type {
+        fn eq(&self, other: &Self) -> bool {
+            self.as_ref() == other.as_ref()
+        }
+    }
+
+    impl Eq for Type {}
+
+    impl Hash for Type {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.as_ref().hash(state)
+        }
+    }
+
+    #[test]
+    fn probes_set_with_str() {
+        let types = HashSet::from([Type::Grass, Type::Fire]);
+        assert!(types.contains("Grass"));
+        assert!(types.contains("Fire"));
+        assert!(!types.contains("Water"));
+        assert!(!types.contains(Type::Water.as_ref()));
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod hash_by_label {
+    extern crate std;
+
+    use core::hash::BuildHasher;
+    use serde_string_enum::SerializeLabeledStringEnum;
+    use std::hash::RandomState;
+
+    #[derive(Debug, PartialEq, Eq, SerializeLabeledStringEnum)]
+    #[string_enum(hash_by_label)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq, Eq, SerializeLabeledStringEnum)]
+    #[string_enum(hash_by_label)]
+    enum Reordered {
+        #[string = "Fire"]
+        Fire,
+        #[string = "Grass"]
+        Grass,
+    }
+
+    #[test]
+    fn hashes_label() {
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(Type::Grass), state.hash_one("Grass"));
+        assert_eq!(state.hash_one(Type::Fire), state.hash_one(Reordered::Fire));
+        assert_eq!(
+            state.hash_one(Type::Grass),
+            state.hash_one(Reordered::Grass)
+        );
+    }
+}
+
+#[cfg(test)]
+mod alt_labels {
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum)]
+    enum Type {
+        #[string("Fire", alt = "Fire Type")]
+        Fire,
+        #[string = "Water"]
+        Water,
+    }
+
+    #[test]
+    fn alternate_flag_writes_alt() {
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        assert_eq!(format(format_args!("{}", Type::Fire)), "Fire");
+        assert_eq!(format(format_args!("{:#}", Type::Fire)), "Fire Type");
+        assert_eq!(format(format_args!("{:>#11}", Type::Fire)), "  Fire Type");
+        assert_eq!(format(format_args!("{:#}", Type::Water)), "Water");
+    }
+
+    #[test]
+    fn serializes_label() {
+        assert_eq!(serde_json::to_string(&Type::Fire).unwrap(), "\"Fire\"");
+    }
+}
+
+#[cfg(test)]
+mod convert_from {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    mod v1 {
+        use serde_string_enum::SerializeLabeledStringEnum;
+
+        #[derive(Debug, PartialEq, SerializeLabeledStringEnum)]
+        pub enum Type {
+            #[string = "Grass"]
+            Grass,
+            #[string = "Fire"]
+            Fire,
+            #[string = "Dark"]
+            Dark,
+        }
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(convert_from = v1::Type)]
+    enum Type {
+        #[string = "Fire"]
+        Fire,
+        #[string = "Leaf"]
+        #[alias = "Grass"]
+        Grass,
+    }
+
+    #[test]
+    fn converts_by_label() {
+        assert_eq!(Type::try_from(v1::Type::Fire), Ok(Type::Fire));
+        assert_eq!(Type::try_from(v1::Type::Grass), Ok(Type::Grass));
+    }
+
+    #[test]
+    fn gives_back_unknown_value() {
+        assert_eq!(Type::try_from(v1::Type::Dark), Err(v1::Type::Dark));
+    }
+}
+
+#[cfg(test)]
+mod subset_of {
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+        #[string = "Water"]
+        Water,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum)]
+    #[string_enum(subset_of = Type)]
+    enum StarterType {
+        #[string = "Water"]
+        Water,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[test]
+    fn compiles_when_labels_are_shared() {
+        assert_eq!(StarterType::Fire.as_ref(), Type::Fire.as_ref());
+        assert_eq!(StarterType::Water.as_ref(), Type::Water.as_ref());
+        assert_eq!(Type::Grass.as_ref(), "Grass");
+    }
+}
+
+#[cfg(test)]
+mod from_bytes {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(default = Unknown)]
+    enum Lenient {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Unknown"]
+        Unknown,
+    }
+
+    #[test]
+    fn parses_labels() {
+        assert_eq!(Type::from_bytes(b"Grass"), Ok(Type::Grass));
+        assert_eq!(Type::from_bytes(b"Flame"), Ok(Type::Fire));
+        assert_eq!(Lenient::from_bytes(b"Grass"), Ok(Lenient::Grass));
+    }
+
+    #[test]
+    fn fails_like_from_str() {
+        assert_eq!(
+            Type::from_bytes(b"Water"),
+            <Type as core::str::FromStr>::from_str("Water")
+        );
+        assert_eq!(Lenient::from_bytes(b"Water"), Ok(Lenient::Unknown));
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn reports_invalid_utf8_lossily() {
+        let error = Type::from_bytes(b"Gr\xffss").unwrap_err();
+        assert_eq!(error.input, "Gr\u{fffd}ss");
+        assert_eq!(Lenient::from_bytes(b"\xff"), Ok(Lenient::Unknown));
+    }
+}
+
+#[cfg(test)]
+mod label_constants {
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum)]
+    #[string_enum(label_constants)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Dark Fire"]
+        DarkFire,
+        #[string = "HTTP Server"]
+        HTTPServer,
+        #[string = "Gen 2 Starter"]
+        Gen2Starter,
+    }
+
+    #[test]
+    fn names_constants_after_variants() {
+        assert_eq!(Type::GRASS_LABEL, "Grass");
+        assert_eq!(Type::DARK_FIRE_LABEL, "Dark Fire");
+        assert_eq!(Type::HTTP_SERVER_LABEL, "HTTP Server");
+        assert_eq!(Type::GEN2_STARTER_LABEL, "Gen 2 Starter");
+    }
+
+    #[test]
+    fn constants_match_labels() {
+        assert_eq!(Type::Grass.as_ref(), Type::GRASS_LABEL);
+        assert_eq!(Type::DarkFire.as_ref(), Type::DARK_FIRE_LABEL);
+        assert_eq!(Type::HTTPServer.as_ref(), Type::HTTP_SERVER_LABEL);
+        assert_eq!(Type::Gen2Starter.as_ref(), Type::GEN2_STARTER_LABEL);
+    }
+}
+
+#[cfg(test)]
+mod parse_str {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn parses_labels() {
+        assert_eq!(Type::parse_str("Grass"), Ok(Type::Grass));
+        assert_eq!(Type::parse_str("Flame"), Ok(Type::Fire));
+    }
+
+    #[test]
+    fn error_borrows_input() {
+        let input = "Water";
+        let error = Type::parse_str(input).unwrap_err();
+        assert_eq!(error, TypeBorrowedParseError { input: "Water" });
+        assert!(core::ptr::eq(error.input, input));
+    }
+
+    #[test]
+    fn formats_error() {
+        extern crate alloc;
+        use alloc::fmt::format;
+
+        let error = Type::parse_str("Water").unwrap_err();
+        assert_eq!(format(format_args!("{error}")), "invalid Type: Water");
+    }
+}
+
+#[cfg(test)]
+mod self_describing {
+    use serde::{
+        de::{
+            value::Error,
+            Error as _,
+            Visitor,
+        },
+        Deserialize,
+        Deserializer,
+    };
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(self_describing)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    /// A deserializer that only presents its string through `deserialize_any`.
+    struct AnyOnly(&'static str);
+
+    impl<'de> Deserializer<'de> for AnyOnly {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_str(self.0)
+        }
+
+        fn deserialize_str<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+            Err(Error::custom("string hint is not supported"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    #[test]
+    fn deserializes_through_deserialize_any() {
+        assert_eq!(Type::deserialize(AnyOnly("Grass")), Ok(Type::Grass));
+        assert_eq!(Type::deserialize(AnyOnly("Fire")), Ok(Type::Fire));
+    }
+
+    #[test]
+    fn rejects_other_values() {
+        assert!(serde_json::from_str::<Type>("1").is_err());
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Fire\"").unwrap(),
+            Type::Fire
+        );
+    }
+}
+
+#[cfg(all(test, feature = "unicase"))]
+mod full_case_folding {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Street {
+        #[string = "Straße"]
+        Strasse,
+        #[string = "FUSS"]
+        Fuss,
+    }
+
+    #[test]
+    fn sharp_s_matches_double_s() {
+        assert_eq!(Street::from_str("STRASSE"), Ok(Street::Strasse));
+        assert_eq!(Street::from_str("strasse"), Ok(Street::Strasse));
+        assert_eq!(Street::from_str("Fuß"), Ok(Street::Fuss));
+    }
+
+    #[test]
+    fn capital_sharp_s_matches() {
+        assert_eq!(Street::from_str("STRAẞE"), Ok(Street::Strasse));
+        assert_eq!(Street::from_str("FUẞ"), Ok(Street::Fuss));
+    }
+
+    #[test]
+    fn deserializes_folded_spellings() {
+        assert_eq!(
+            serde_json::from_str::<Street>("\"STRASSE\"").unwrap(),
+            Street::Strasse
+        );
+        assert_eq!(
+            serde_json::from_str::<Street>("\"fuß\"").unwrap(),
+            Street::Fuss
+        );
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(completions, parse_prefix)]
+    enum Road {
+        #[string = "Straße"]
+        Strasse,
+        #[string = "Weg"]
+        Way,
+    }
+
+    #[test]
+    fn completes_folded_prefixes() {
+        assert_eq!(Road::completions("STRASS").next(), Some("Straße"));
+        assert_eq!(Road::completions("strasse").next(), Some("Straße"));
+        assert_eq!(Road::completions("STRASSEN").next(), None);
+    }
+
+    #[test]
+    fn parses_folded_prefixes() {
+        assert_eq!(Road::parse_prefix("STRASSE 5"), Some((Road::Strasse, " 5")));
+        assert_eq!(Road::parse_prefix("straẞe"), Some((Road::Strasse, "")));
+        assert_eq!(Road::parse_prefix("STRAS"), None);
+    }
+}
+
+#[cfg(all(test, feature = "unicase"))]
+mod simple_case_folding {
+    use core::str::FromStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(case_fold = "simple")]
+    enum Street {
+        #[string = "Straße"]
+        Strasse,
+        #[string = "FUSS"]
+        Fuss,
+        #[string = "Οδός"]
+        Odos,
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum FullStreet {
+        #[string = "Οδός"]
+        Odos,
+    }
+
+    #[test]
+    fn sharp_s_does_not_match_double_s() {
+        assert!(Street::from_str("STRASSE").is_err());
+        assert!(Street::from_str("strasse").is_err());
+        assert!(Street::from_str("Fuß").is_err());
+        assert!(serde_json::from_str::<Street>("\"STRASSE\"").is_err());
+    }
+
+    #[test]
+    fn capital_sharp_s_matches() {
+        assert_eq!(Street::from_str("STRAẞE"), Ok(Street::Strasse));
+        assert_eq!(Street::from_str("straße"), Ok(Street::Strasse));
+        assert_eq!(Street::from_str("fuss"), Ok(Street::Fuss));
+        assert_eq!(
+            serde_json::from_str::<Street>("\"STRAẞE\"").unwrap(),
+            Street::Strasse
+        );
+    }
+
+    #[test]
+    fn final_sigma_matches_under_both_foldings() {
+        for spelling in ["ΟΔΌΣ", "οδόσ", "οδός", "ΟΔΌς"] {
+            assert_eq!(Street::from_str(spelling), Ok(Street::Odos));
+            assert_eq!(FullStreet::from_str(spelling), Ok(FullStreet::Odos));
+        }
+    }
+}
+
+#[cfg(test)]
+mod remote {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    mod other_crate {
+        #[derive(Debug, PartialEq)]
+        pub enum Type {
+            Grass,
+            Fire,
+        }
+    }
+
+    #[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(remote = "other_crate::Type")]
+    enum TypeDef {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn serializes_remote_value() {
+        let value = TypeDef::serialize(&other_crate::Type::Fire, serde_json::value::Serializer);
+        assert_eq!(value.unwrap(), serde_json::Value::from("Fire"));
+    }
+
+    #[test]
+    fn deserializes_remote_value() {
+        let mut deserializer = serde_json::Deserializer::from_str("\"Grass\"");
+        assert_eq!(
+            TypeDef::deserialize(&mut deserializer).unwrap(),
+            other_crate::Type::Grass
+        );
+        let mut deserializer = serde_json::Deserializer::from_str("\"Flame\"");
+        assert_eq!(
+            TypeDef::deserialize(&mut deserializer).unwrap(),
+            other_crate::Type::Fire
+        );
+    }
+}
+
+#[cfg(test)]
+mod with_module {
+    use serde::{
+        Serialize,
+        Serializer,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(with_module = type_as_string, generate_tests)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    // The enum keeps its own representation outside of fields using the module.
+    impl Serialize for Type {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_u8(self.index() as u8)
+        }
+    }
+
+    #[test]
+    fn module_serializes_label() {
+        let value = type_as_string::serialize(&Type::Fire, serde_json::value::Serializer);
+        assert_eq!(value.unwrap(), serde_json::Value::from("Fire"));
+        assert_eq!(serde_json::to_string(&Type::Fire).unwrap(), "1");
+    }
+
+    #[test]
+    fn module_deserializes_label() {
+        let mut deserializer = serde_json::Deserializer::from_str("\"Flame\"");
+        assert_eq!(
+            type_as_string::deserialize(&mut deserializer).unwrap(),
+            Type::Fire
+        );
+        let mut deserializer = serde_json::Deserializer::from_str("\"Grass\"");
+        assert_eq!(
+            type_as_string::deserialize(&mut deserializer).unwrap(),
+            Type::Grass
+        );
+    }
+}
+
+#[cfg(test)]
+mod prost {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    mod proto {
+        // The shape of an enum generated by prost.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[repr(i32)]
+        pub enum Type {
+            Unspecified = 0,
+            Grass = 1,
+            Fire = 2,
+        }
+
+        impl TryFrom<i32> for Type {
+            type Error = ();
+
+            fn try_from(value: i32) -> Result<Self, ()> {
+                match value {
+                    0 => Ok(Self::Unspecified),
+                    1 => Ok(Self::Grass),
+                    2 => Ok(Self::Fire),
+                    _ => Err(()),
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(prost = proto::Type)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[test]
+    fn converts_to_proto() {
+        assert_eq!(proto::Type::from(Type::Grass), proto::Type::Grass);
+        assert_eq!(i32::from(Type::Fire), 2);
+    }
+
+    #[test]
+    fn converts_from_proto() {
+        assert_eq!(Type::try_from(proto::Type::Fire), Ok(Type::Fire));
+        assert_eq!(
+            Type::try_from(proto::Type::Unspecified),
+            Err(proto::Type::Unspecified)
+        );
+        assert_eq!(Type::try_from(1), Ok(Type::Grass));
+        assert_eq!(Type::try_from(0), Err(0));
+        assert_eq!(Type::try_from(7), Err(7));
+    }
+}
+
+#[cfg(test)]
+mod c_ffi {
+    use core::ffi::CStr;
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(c_ffi = "pokedex_")]
+    pub enum MoveCategory {
+        #[string = "Physical"]
+        Physical,
+        #[string = "Special"]
+        #[alias = "Spec"]
+        Special,
+        #[string = "Status"]
+        Status,
+    }
+
+    #[test]
+    fn returns_static_labels() {
+        let label = pokedex_move_category_label(1);
+        assert_eq!(unsafe { CStr::from_ptr(label) }, c"Special");
+        assert_eq!(
+            unsafe { CStr::from_ptr(pokedex_move_category_label(2)) },
+            c"Status"
+        );
+        assert!(pokedex_move_category_label(3).is_null());
+    }
+
+    #[test]
+    fn parses_labels_to_indices() {
+        unsafe {
+            assert_eq!(pokedex_move_category_from_label(c"Physical".as_ptr()), 0);
+            assert_eq!(pokedex_move_category_from_label(c"Spec".as_ptr()), 1);
+            assert_eq!(pokedex_move_category_from_label(c"Dragon".as_ptr()), -1);
+            assert_eq!(pokedex_move_category_from_label(core::ptr::null()), -1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod all_accepted_strings {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Plant"]
+        #[alias = "Leaf"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(serialize_case = "lowercase")]
+    enum Cased {
+        #[string = "Grass"]
+        Grass,
+        #[string = "fire"]
+        Fire,
+    }
+
+    #[test]
+    fn yields_labels_and_aliases() {
+        assert!(Type::all_accepted_strings().eq(["Grass", "Plant", "Leaf", "Fire"]));
+    }
+
+    #[test]
+    fn yields_cased_labels() {
+        assert!(Cased::all_accepted_strings().eq(["Grass", "grass", "fire"]));
+    }
+}
+
+#[cfg(test)]
+mod label_ids {
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+        #[string("Water", id = 10)]
+        Water,
+    }
+
+    const FIRE_ID: u32 = Type::Fire.label_id();
+
+    #[test]
+    fn converts_ids() {
+        assert_eq!(Type::Grass.label_id(), 0);
+        assert_eq!(FIRE_ID, 1);
+        assert_eq!(Type::Water.label_id(), 10);
+        assert_eq!(Type::from_label_id(10), Some(Type::Water));
+        assert_eq!(Type::from_label_id(1), Some(Type::Fire));
+        assert_eq!(Type::from_label_id(2), None);
+    }
+
+    #[test]
+    fn converts_indices() {
+        let names = ["grass", "fire", "water"];
+        assert_eq!(names[Type::Water.index()], "water");
+        assert_eq!(Type::Fire.index(), 1);
+        assert_eq!(Type::from_index(2), Some(Type::Water));
+        assert_eq!(Type::from_index(3), None);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod label_map {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Plant"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[test]
+    fn maps_labels_and_aliases() {
+        let map = Type::label_map();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get("Grass"), Some(&Type::Grass));
+        assert_eq!(map.get("Plant"), Some(&Type::Grass));
+        assert_eq!(map.get("Fire"), Some(&Type::Fire));
+        assert_eq!(map.get("Water"), None);
+    }
+}
+
+#[cfg(test)]
+mod minicbor {
+    extern crate alloc;
+
+    use alloc::vec::Vec;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(minicbor)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn encodes_label_as_text() {
+        let mut expected = Vec::new();
+        minicbor::encode("Fire", &mut expected).unwrap();
+        assert_eq!(minicbor::to_vec(Type::Fire).unwrap(), expected);
+    }
+
+    #[test]
+    fn decodes_labels_and_aliases() {
+        let bytes = minicbor::to_vec(Type::Grass).unwrap();
+        assert_eq!(minicbor::decode::<Type>(&bytes).unwrap(), Type::Grass);
+        let bytes = minicbor::to_vec("Flame").unwrap();
+        assert_eq!(minicbor::decode::<Type>(&bytes).unwrap(), Type::Fire);
+    }
+
+    #[test]
+    fn fails_to_decode_unknown_label() {
+        let bytes = minicbor::to_vec("Water").unwrap();
+        assert!(minicbor::decode::<Type>(&bytes).is_err());
+        let bytes = minicbor::to_vec(1u8).unwrap();
+        assert!(minicbor::decode::<Type>(&bytes).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod zbus {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+    use zvariant::{
+        OwnedValue,
+        Signature,
+        Type as _,
+        Value,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(zbus)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn has_string_signature() {
+        assert_eq!(Type::SIGNATURE, &Signature::Str);
+    }
+
+    #[test]
+    fn converts_to_values() {
+        assert_eq!(Value::from(Type::Fire), Value::from("Fire"));
+        assert_eq!(
+            Value::from(OwnedValue::from(Type::Grass)),
+            Value::from("Grass")
+        );
+    }
+
+    #[test]
+    fn converts_from_values() {
+        assert_eq!(Type::try_from(Value::from("Flame")), Ok(Type::Fire));
+        assert_eq!(
+            Type::try_from(OwnedValue::from(Type::Grass)),
+            Ok(Type::Grass)
+        );
+        assert!(Type::try_from(Value::from("Water")).is_err());
+        assert!(Type::try_from(Value::from(1u32)).is_err());
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(zbus, qualified, serialize_case = "lowercase")]
+    enum QualifiedType {
+        #[string = "Grass"]
+        Grass,
+    }
+
+    // `Value` conversions go through `Display` and `FromStr`, so `serialize_case` and `qualified`
+    // do not apply.
+    #[test]
+    fn converts_labels_as_displayed() {
+        assert_eq!(Value::from(QualifiedType::Grass), Value::from("Grass"));
+        assert_eq!(
+            QualifiedType::try_from(Value::from(QualifiedType::Grass)),
+            Ok(QualifiedType::Grass)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "nom"))]
+mod nom {
+    use nom::{
+        character::complete::char,
+        sequence::separated_pair,
+        Parser,
+    };
+    use serde_string_enum::DeserializeLabeledStringEnum;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Fire"]
+        Fire,
+        #[string = "Fire Fang"]
+        #[alias = "FireFang"]
+        FireFang,
+        #[string = "Grass"]
+        Grass,
+    }
+
+    #[test]
+    fn parses_longest_label() {
+        assert_eq!(Type::parser("Fire Fang!"), Ok(("!", Type::FireFang)));
+        assert_eq!(Type::parser("Fire!"), Ok(("!", Type::Fire)));
+        assert_eq!(Type::parser("FireFang"), Ok(("", Type::FireFang)));
+    }
+
+    #[test]
+    fn fails_without_label() {
+        assert_eq!(
+            Type::parser("Water"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "Water",
+                nom::error::ErrorKind::Tag
+            )))
+        );
+    }
+
+    #[test]
+    fn embeds_in_grammars() {
+        let mut pair = separated_pair(Type::parser, char(','), Type::parser);
+        assert_eq!(
+            pair.parse("Grass,Fire Fang;"),
+            Ok((";", (Type::Grass, Type::FireFang)))
+        );
+    }
+}
+
+#[cfg(all(test, feature = "uniffi"))]
+mod uniffi {
+    use serde_string_enum::DeserializeLabeledStringEnum;
+    // The scaffolding generated by `uniffi` names `Vec` without a path.
+    use std::{
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    };
+    use uniffi::{
+        Lift,
+        Lower,
+    };
+
+    use crate::UniFfiTag;
+
+    #[derive(Debug, PartialEq, DeserializeLabeledStringEnum)]
+    #[string_enum(uniffi)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    fn lower_str(s: &str) -> uniffi::RustBuffer {
+        <String as Lower<UniFfiTag>>::lower(String::from(s))
+    }
+
+    #[test]
+    fn lowers_labels() {
+        let buffer = <Type as Lower<UniFfiTag>>::lower(Type::Fire);
+        assert_eq!(
+            <String as Lift<UniFfiTag>>::try_lift(buffer).unwrap(),
+            "Fire"
+        );
+    }
+
+    #[test]
+    fn lifts_labels_and_aliases() {
+        assert_eq!(
+            <Type as Lift<UniFfiTag>>::try_lift(lower_str("Grass")).unwrap(),
+            Type::Grass
+        );
+        assert_eq!(
+            <Type as Lift<UniFfiTag>>::try_lift(lower_str("Flame")).unwrap(),
+            Type::Fire
+        );
+    }
+
+    #[test]
+    fn fails_to_lift_unknown_labels() {
+        assert_eq!(
+            <Type as Lift<UniFfiTag>>::try_lift(lower_str("Water"))
+                .unwrap_err()
+                .to_string(),
+            "invalid Type: Water"
+        );
+    }
+}
+
+#[cfg(test)]
+mod heapless {
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(SerializeLabeledStringEnum)]
+    #[string_enum(heapless)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Electric"]
+        Electric,
+    }
+
+    #[derive(SerializeLabeledStringEnum)]
+    #[string_enum(heapless)]
+    enum Event<'a> {
+        #[string = "Click"]
+        Click,
+        #[string(other)]
+        Other(&'a str),
+    }
+
+    #[test]
+    fn copies_labels() {
+        assert_eq!(Type::MAX_LABEL_LEN, 8);
+        assert_eq!(Type::Grass.to_heapless::<8>().unwrap(), "Grass");
+        assert_eq!(Type::Electric.to_heapless::<16>().unwrap(), "Electric");
+    }
+
+    #[test]
+    fn fails_for_long_strings() {
+        assert_eq!(Event::Click.to_heapless::<5>().unwrap(), "Click");
+        assert_eq!(Event::Other("Hover").to_heapless::<5>().unwrap(), "Hover");
+        assert!(Event::Other("Scroll").to_heapless::<5>().is_err());
+    }
+}
+
+// Each enum generates its own test module, which runs alongside these tests.
+#[cfg(test)]
+mod generate_tests {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(generate_tests, qualified)]
+    enum Type {
+        #[string = "Grass"]
+        #[alias = "Plant"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        #[alias = "Blaze"]
+        Fire,
+        #[string = "Water"]
+        Water,
+    }
+
+    #[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(generate_tests)]
+    enum Event<'a> {
+        #[string = "Click"]
+        #[alias = "Press"]
+        Click,
+        #[string = "Press"]
+        Press,
+        #[string(other)]
+        Other(&'a str),
+    }
+}
+
+#[cfg(test)]
+mod const_labels {
+    use core::str::FromStr;
+    use serde_string_enum::{
+        DebugLabeledStringEnum,
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    const GRASS: &str = "Grass";
+
+    mod labels {
+        pub const FIRE: &str = "Fire";
+    }
+
+    #[derive(
+        PartialEq, DebugLabeledStringEnum, SerializeLabeledStringEnum, DeserializeLabeledStringEnum,
+    )]
+    enum Type {
+        #[string(GRASS)]
+        Grass,
+        #[string(labels::FIRE)]
+        Fire,
+        #[string("Water")]
+        Water,
+        #[string = concat!("Elec", "tric")]
+        #[alias = concat!("Thunder", "bolt")]
+        Electric,
+        #[string = env!("CARGO_PKG_NAME")]
+        Package,
+    }
+
+    #[test]
+    fn serializes_constant_labels() {
+        assert_eq!(serde_json::to_string(&Type::Grass).unwrap(), "\"Grass\"");
+        assert_eq!(serde_json::to_string(&Type::Fire).unwrap(), "\"Fire\"");
+        assert_eq!(serde_json::to_string(&Type::Water).unwrap(), "\"Water\"");
+        assert_eq!(Type::Fire.label_bytes(), b"Fire");
+        assert_eq!(Type::Electric.label_bytes(), b"Electric");
+    }
+
+    #[test]
+    fn deserializes_constant_labels() {
+        assert_eq!(
+            serde_json::from_str::<Type>("\"Grass\"").unwrap(),
+            Type::Grass
+        );
+        assert_eq!(Type::from_str(labels::FIRE).unwrap(), Type::Fire);
+        assert_eq!(Type::from_str("Water").unwrap(), Type::Water);
+    }
+
+    #[test]
+    fn expands_macro_labels() {
+        assert_eq!(
+            serde_json::to_string(&Type::Electric).unwrap(),
+            "\"Electric\""
+        );
+        assert_eq!(Type::from_str("Electric").unwrap(), Type::Electric);
+        assert_eq!(Type::from_str("Thunderbolt").unwrap(), Type::Electric);
+        assert_eq!(Type::from_str("serde_string_enum").unwrap(), Type::Package);
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "alloc")))]
+mod json {
+    use core::convert::TryFrom;
+    use serde_json::Value;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(json)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn converts_to_value() {
+        assert_eq!(Value::from(Type::Grass), Value::String("Grass".into()));
+        assert_eq!(Value::from(Type::Fire), Value::String("Fire".into()));
+    }
+
+    #[test]
+    fn converts_from_value() {
+        assert_eq!(
+            Type::try_from(&Value::String("Fire".into())).unwrap(),
+            Type::Fire
+        );
+        assert_eq!(
+            Type::try_from(&Value::String("Flame".into())).unwrap(),
+            Type::Fire
+        );
+        assert!(Type::try_from(&Value::String("Water".into())).is_err());
+        assert!(Type::try_from(&Value::Bool(true)).is_err());
+    }
+
+    #[test]
+    fn lists_labels() {
+        assert_eq!(Type::labels_json(), serde_json::json!(["Grass", "Fire"]));
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(json, qualified, serialize_case = "lowercase")]
+    enum QualifiedType {
+        #[string = "Grass"]
+        Grass,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(json, serialize_as = "number")]
+    enum NumberedType {
+        #[string = "Grass"]
+        #[number = 12]
+        Grass,
+    }
+
+    #[test]
+    fn converts_as_serialized() {
+        assert_eq!(
+            Value::from(QualifiedType::Grass),
+            serde_json::to_value(QualifiedType::Grass).unwrap()
+        );
+        assert_eq!(
+            Value::from(QualifiedType::Grass),
+            Value::String("QualifiedType::grass".into())
+        );
+        assert_eq!(
+            QualifiedType::try_from(&Value::from(QualifiedType::Grass)).unwrap(),
+            QualifiedType::Grass
+        );
+        assert_eq!(Value::from(NumberedType::Grass), serde_json::json!(12));
+        assert_eq!(
+            NumberedType::try_from(&serde_json::json!(12)).unwrap(),
+            NumberedType::Grass
+        );
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod pyo3 {
+    use pyo3::{
+        types::{
+            PyAnyMethods,
+            PyInt,
+            PyStringMethods,
+        },
+        IntoPyObject,
+        Python,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(pyo3)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn converts_to_and_from_python_strings() {
+        Python::attach(|py| {
+            let object = Type::Fire.into_pyobject(py).unwrap();
+            assert_eq!(object.to_str().unwrap(), "Fire");
+            assert_eq!(object.extract::<Type>().unwrap(), Type::Fire);
+            let alias = "Flame".into_pyobject(py).unwrap();
+            assert_eq!(alias.extract::<Type>().unwrap(), Type::Fire);
+        });
+    }
+
+    #[test]
+    fn fails_to_extract_unknown_values() {
+        Python::attach(|py| {
+            assert!("Water"
+                .into_pyobject(py)
+                .unwrap()
+                .extract::<Type>()
+                .is_err());
+            assert!(PyInt::new(py, 1).extract::<Type>().is_err());
+        });
+    }
+}
+
+#[cfg(all(test, any(feature = "std", feature = "alloc")))]
+mod wasm_bindgen {
+    use core::convert::TryFrom;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+    use wasm_bindgen::JsValue;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(wasm_bindgen)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(wasm_bindgen, serialize_case = "lowercase")]
+    enum LowercaseType {
+        #[string = "Grass"]
+        Grass,
+    }
+
+    // `JsValue` can only be constructed in a JavaScript host, so the round trip only runs on
+    // wasm32.
+    #[allow(dead_code)]
+    fn round_trip() {
+        assert_eq!(
+            JsValue::from(Type::Grass).as_string().as_deref(),
+            Some("Grass")
+        );
+        let value = JsValue::from(Type::Fire);
+        assert_eq!(value.as_string().as_deref(), Some("Fire"));
+        assert_eq!(Type::try_from(value).unwrap(), Type::Fire);
+        assert_eq!(
+            Type::try_from(JsValue::from_str("Flame")).unwrap(),
+            Type::Fire
+        );
+        assert!(Type::try_from(JsValue::from_str("Water")).is_err());
+        assert!(Type::try_from(JsValue::from_f64(1.0)).is_err());
+
+        // `JsValue` conversions go through `Display` and `FromStr`, so `serialize_case` does not
+        // apply.
+        let value = JsValue::from(LowercaseType::Grass);
+        assert_eq!(value.as_string().as_deref(), Some("Grass"));
+        assert_eq!(
+            LowercaseType::try_from(value).unwrap(),
+            LowercaseType::Grass
+        );
+    }
+
+    #[test]
+    fn implements_conversions() {
+        fn assert_conversions<T: Into<JsValue> + TryFrom<JsValue, Error = JsValue>>() {}
+        assert_conversions::<Type>();
+        assert_conversions::<LowercaseType>();
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn converts_to_and_from_js_strings() {
+        round_trip();
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod sea_orm {
+    use sea_orm::{
+        sea_query::{
+            Nullable,
+            ValueType,
+        },
+        ActiveEnum,
+        Iterable,
+        Value,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+    use std::{
+        string::String,
+        vec::Vec,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(sea_orm)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn converts_to_and_from_values() {
+        assert_eq!(Type::Fire.to_value(), "Fire");
+        assert_eq!(
+            Type::try_from_value(&String::from("Flame")).unwrap(),
+            Type::Fire
+        );
+        assert!(Type::try_from_value(&String::from("Water")).is_err());
+
+        let value = Value::from(Type::Grass);
+        assert_eq!(
+            value,
+            Value::String(Some(std::boxed::Box::new(String::from("Grass"))))
+        );
+        assert_eq!(<Type as ValueType>::try_from(value).unwrap(), Type::Grass);
+        assert!(<Type as ValueType>::try_from(Value::Int(Some(1))).is_err());
+        assert_eq!(<Type as Nullable>::null(), Value::String(None));
+    }
+
+    #[test]
+    fn iterates_over_variants() {
+        assert_eq!(Type::iter().collect::<Vec<_>>(), [Type::Grass, Type::Fire]);
+        assert_eq!(Type::name().to_string(), "Type");
+    }
+}
+
+#[cfg(test)]
+mod defmt {
+    use defmt::{
+        export::{
+            fetch_bytes,
+            make_formatter,
+        },
+        Format,
+    };
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(SerializeLabeledStringEnum)]
+    #[string_enum(defmt)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[test]
+    fn writes_labels() {
+        Type::Fire.format(make_formatter());
+        assert!(fetch_bytes().ends_with(b"Fire"));
+        Type::Grass.format(make_formatter());
+        assert!(fetch_bytes().ends_with(b"Grass"));
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod bson {
+    use bson::Bson;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+    use std::string::String;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(bson)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn converts_to_and_from_bson() {
+        assert_eq!(Bson::from(Type::Fire), Bson::String(String::from("Fire")));
+        assert_eq!(
+            Type::try_from(Bson::from(Type::Grass)).unwrap(),
+            Type::Grass
+        );
+        assert_eq!(
+            Type::try_from(Bson::String(String::from("Flame"))).unwrap(),
+            Type::Fire
+        );
+        assert!(Type::try_from(Bson::String(String::from("Water"))).is_err());
+        assert!(Type::try_from(Bson::Int32(1)).is_err());
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(bson, qualified, serialize_case = "lowercase")]
+    enum QualifiedType {
+        #[string = "Grass"]
+        Grass,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(bson, serialize_as = "number")]
+    enum NumberedType {
+        #[string = "Grass"]
+        #[number = 12]
+        Grass,
+    }
+
+    #[test]
+    fn converts_as_serialized() {
+        assert_eq!(
+            Bson::from(QualifiedType::Grass),
+            bson::to_bson(&QualifiedType::Grass).unwrap()
+        );
+        assert_eq!(
+            Bson::from(QualifiedType::Grass),
+            Bson::String(String::from("QualifiedType::grass"))
+        );
+        assert_eq!(
+            QualifiedType::try_from(Bson::from(QualifiedType::Grass)).unwrap(),
+            QualifiedType::Grass
+        );
+        assert_eq!(Bson::from(NumberedType::Grass), Bson::Int64(12));
+        assert_eq!(
+            NumberedType::try_from(Bson::Int64(12)).unwrap(),
+            NumberedType::Grass
+        );
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod redis {
+    use redis::{
+        FromRedisValue,
+        ToRedisArgs,
+        Value,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+    use std::{
+        string::String,
+        vec,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(redis)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn writes_labels_as_arguments() {
+        assert_eq!(Type::Fire.to_redis_args(), vec![b"Fire".to_vec()]);
+    }
+
+    #[test]
+    fn parses_replies() {
+        assert_eq!(
+            Type::from_redis_value(&Value::BulkString(b"Grass".to_vec())).unwrap(),
+            Type::Grass
+        );
+        assert_eq!(
+            Type::from_redis_value(&Value::SimpleString(String::from("Flame"))).unwrap(),
+            Type::Fire
+        );
+        assert!(Type::from_redis_value(&Value::BulkString(b"Water".to_vec())).is_err());
+        assert!(Type::from_redis_value(&Value::Nil).is_err());
+    }
+}
+
+#[cfg(test)]
+mod rkyv {
+    extern crate alloc;
+    use alloc::string::String;
+    use rkyv::{
+        primitive::ArchivedU32,
+        rancor::Error,
+        string::ArchivedString,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(rkyv)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(rkyv, archive_as = "index")]
+    enum Weather {
+        #[string = "Sun"]
+        Sun,
+        #[string = "Rain"]
+        Rain,
+    }
+
+    #[test]
+    fn archives_labels() {
+        let bytes = rkyv::to_bytes::<Error>(&Type::Fire).unwrap();
+        let archived = rkyv::access::<ArchivedString, Error>(&bytes).unwrap();
+        assert_eq!(archived.as_str(), "Fire");
+        assert_eq!(
+            rkyv::deserialize::<Type, Error>(archived).unwrap(),
+            Type::Fire
+        );
+
+        let bytes = rkyv::to_bytes::<Error>(&String::from("Water")).unwrap();
+        let archived = rkyv::access::<ArchivedString, Error>(&bytes).unwrap();
+        assert!(rkyv::deserialize::<Type, Error>(archived).is_err());
+    }
+
+    #[test]
+    fn archives_indices() {
+        let bytes = rkyv::to_bytes::<Error>(&Weather::Rain).unwrap();
+        let archived = rkyv::access::<ArchivedU32, Error>(&bytes).unwrap();
+        assert_eq!(archived.to_native(), 1);
+        assert_eq!(
+            rkyv::deserialize::<Weather, Error>(archived).unwrap(),
+            Weather::Rain
+        );
+
+        let bytes = rkyv::to_bytes::<Error>(&2u32).unwrap();
+        let archived = rkyv::access::<ArchivedU32, Error>(&bytes).unwrap();
+        assert!(rkyv::deserialize::<Weather, Error>(archived).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod dialoguer {
+    use serde_string_enum::SerializeLabeledStringEnum;
+
+    #[derive(SerializeLabeledStringEnum)]
+    #[string_enum(dialoguer)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    // Prompting needs an interactive terminal, which would block the test run when there is one.
+    #[test]
+    fn generates_prompt_select() {
+        let prompt_select: fn(&str) -> dialoguer::Result<Type> = Type::prompt_select;
+        let _ = prompt_select;
+    }
+}
+
+#[cfg(all(test, feature = "headers"))]
+mod headers {
+    use headers::{
+        Header,
+        HeaderMapExt,
+        HeaderValue,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+    use std::vec::Vec;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(header_name = "x-priority")]
+    enum Priority {
+        #[string = "low"]
+        Low,
+        #[string = "high"]
+        #[alias = "urgent"]
+        High,
+    }
+
+    #[test]
+    fn encodes_and_decodes_header() {
+        assert_eq!(Priority::name(), "x-priority");
+
+        let mut map = headers::HeaderMap::new();
+        map.typed_insert(Priority::High);
+        assert_eq!(map["x-priority"], "high");
+        assert_eq!(map.typed_get::<Priority>(), Some(Priority::High));
+
+        let values = [HeaderValue::from_static("urgent")];
+        assert_eq!(
+            Priority::decode(&mut values.iter()).unwrap(),
+            Priority::High
+        );
+
+        let values = [HeaderValue::from_static("medium")];
+        assert!(Priority::decode(&mut values.iter()).is_err());
+        assert!(Priority::decode(&mut Vec::<HeaderValue>::new().iter()).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod poem_openapi {
+    use poem_openapi::{
+        registry::{
+            MetaSchemaRef,
+            Registry,
+        },
+        types::{
+            ParseFromJSON,
+            ToJSON,
+            Type as _,
+        },
+    };
+    use serde_json::json;
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(poem_openapi)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn converts_to_and_from_json() {
+        assert_eq!(Type::Fire.to_json(), Some(json!("Fire")));
+        assert_eq!(
+            Type::parse_from_json(Some(json!("Flame"))).unwrap(),
+            Type::Fire
+        );
+        assert!(Type::parse_from_json(Some(json!("Water"))).is_err());
+        assert!(Type::parse_from_json(Some(json!(1))).is_err());
+        assert!(Type::parse_from_json(None).is_err());
+    }
+
+    #[test]
+    fn registers_string_schema_listing_labels() {
+        assert_eq!(Type::name(), "Type");
+        assert!(matches!(Type::schema_ref(), MetaSchemaRef::Reference(name) if name == "Type"));
+
+        let mut registry = Registry::new();
+        Type::register(&mut registry);
+        let schema = &registry.schemas["Type"];
+        assert_eq!(schema.ty, "string");
+        assert_eq!(schema.enum_items, [json!("Grass"), json!("Fire")]);
+    }
+}
+
+#[cfg(all(test, feature = "serde_with"))]
+mod serde_with {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+    use serde_with::{
+        de::DeserializeAsWrap,
+        ser::SerializeAsWrap,
+    };
+
+    struct TypeLabel;
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(serde_as = TypeLabel)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[alias = "Flame"]
+        Fire,
+    }
+
+    #[test]
+    fn serializes_through_adapter() {
+        assert_eq!(
+            serde_json::to_string(&SerializeAsWrap::<Type, TypeLabel>::new(&Type::Fire)).unwrap(),
+            "\"Fire\""
+        );
+    }
+
+    #[test]
+    fn deserializes_through_adapter() {
+        assert_eq!(
+            serde_json::from_str::<DeserializeAsWrap<Type, TypeLabel>>("\"Flame\"")
+                .unwrap()
+                .into_inner(),
+            Type::Fire
+        );
+        assert!(serde_json::from_str::<DeserializeAsWrap<Type, TypeLabel>>("\"Water\"").is_err());
+    }
+}
+
+#[cfg(test)]
+mod custom_string_conversion {
+    use core::{
+        fmt::{
+            Display,
+            Formatter,
+        },
+        str::FromStr,
+    };
+
+    use serde_string_enum::{
+        DeserializeStringEnum,
+        SerializeStringEnum,
+    };
+
+    #[derive(Debug, PartialEq)]
+    enum Rotation {
+        Left,
+        Right,
+    }
+
+    impl Display for Rotation {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Self::Left => write!(f, "L"),
+                Self::Right => write!(f, "R"),
+            }
+        }
+    }
+
+    impl FromStr for Rotation {
+        type Err = &'static str;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(match s {
+                "L" => Self::Left,
+                "R" => Self::Right,
+                _ => return Err("invalid rotation"),
+            })
+        }
+    }
+
+    #[derive(Debug, PartialEq, SerializeStringEnum, DeserializeStringEnum)]
+    enum Move {
+        Stay,
+        Forward(u8),
+        Rotate(Rotation),
+    }
+
+    impl Display for Move {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Self::Stay => write!(f, "S"),
+                Self::Forward(n) => write!(f, "F{n}"),
+                Self::Rotate(r) => write!(f, "R{r}"),
+            }
+        }
+    }
+
+    impl FromStr for Move {
+        type Err = &'static str;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(match &s[0..1] {
+                "S" => Self::Stay,
+                "F" => Self::Forward(s[1..].parse::<u8>().map_err(|_| "invalid forward number")?),
+                "R" => Self::Rotate(Rotation::from_str(&s[1..])?),
+                _ => return Err("invalid move"),
+            })
+        }
+    }
+
+    #[test]
+    fn derives_serialize() {
+        extern crate alloc;
+        use alloc::vec;
+
+        assert_eq!(serde_json::to_string(&Move::Stay).unwrap(), "\"S\"");
+        assert_eq!(serde_json::to_string(&Move::Forward(1)).unwrap(), "\"F1\"");
+        assert_eq!(
+            serde_json::to_string(&Move::Rotate(Rotation::Left)).unwrap(),
+            "\"RL\""
+        );
+
+        let moves = vec![
+            Move::Stay,
+            Move::Forward(20),
+            Move::Rotate(Rotation::Right),
+            Move::Forward(10),
+        ];
+        assert_eq!(
+            serde_json::to_string(&moves).unwrap(),
+            "[\"S\",\"F20\",\"RR\",\"F10\"]"
+        );
+    }
+
+    #[test]
+    fn derives_deserialize() {
+        extern crate alloc;
+        use alloc::{
+            vec,
+            vec::Vec,
+        };
+
+        assert_eq!(serde_json::from_str::<Move>("\"S\"").unwrap(), Move::Stay);
+        assert_eq!(
+            serde_json::from_str::<Move>("\"F123\"").unwrap(),
+            Move::Forward(123)
+        );
+        assert_eq!(
+            serde_json::from_str::<Move>("\"RR\"").unwrap(),
+            Move::Rotate(Rotation::Right)
+        );
+
+        assert_eq!(
+            serde_json::from_str::<Vec<Move>>("[\"S\",\"F20\",\"RR\",\"F10\"]").unwrap(),
+            vec![
+                Move::Stay,
+                Move::Forward(20),
+                Move::Rotate(Rotation::Right),
+                Move::Forward(10),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod mixed_derives {
+    use core::fmt::{
+        Display,
+        Formatter,
+    };
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeStringEnum,
+    };
+
+    // The labels are only used by the labeled derive, so the warning of the other is allowed.
+    #[allow(deprecated)]
+    #[derive(Debug, PartialEq, SerializeStringEnum, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "grass"]
+        #[alias = "plant"]
+        Grass,
+        #[string = "fire"]
+        Fire,
+    }
+
+    impl Display for Type {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Self::Grass => f.write_str("GRASS"),
+                Self::Fire => f.write_str("FIRE"),
+            }
+        }
+    }
+
+    #[test]
+    fn serializes_with_display_and_deserializes_labels() {
+        assert_eq!(serde_json::to_string(&Type::Grass).unwrap(), "\"GRASS\"");
+        assert_eq!(
+            serde_json::from_str::<Type>("\"plant\"").unwrap(),
+            Type::Grass
+        );
+        assert_eq!(
+            serde_json::from_str::<Type>("\"fire\"").unwrap(),
+            Type::Fire
+        );
+    }
+}
+
+#[cfg(test)]
+mod tagged {
+    use serde_string_enum::{
+        DeserializeLabeledStringEnum,
+        SerializeLabeledStringEnum,
+    };
+    use serde_string_enum_runtime::{
+        Prefix,
+        Tagged,
+    };
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        Fire,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(qualified = ".", serialize_case = "lowercase")]
+    enum Element {
+        #[string = "Earth"]
+        Earth,
+    }
+
+    #[derive(Debug, PartialEq, SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+    #[string_enum(serialize_as = "number")]
+    enum Numbered {
+        #[string = "One"]
+        #[number = 1]
+        One,
+    }
+
+    struct Event;
+
+    impl Prefix for Event {
+        const PREFIX: &'static str = "evt:";
+    }
+
+    #[test]
+    fn serializes_with_prefix() {
+        assert_eq!(
+            serde_json::to_string(&Tagged::<_, Event>::new(Type::Fire)).unwrap(),
+            "\"evt:Fire\""
+        );
+        assert_eq!(serde_json::to_string(&Type::Fire).unwrap(), "\"Fire\"");
+    }
+
+    #[test]
+    fn deserializes_with_prefix() {
+        assert_eq!(
+            serde_json::from_str::<Tagged<Type, Event>>("\"evt:Grass\"")
+                .unwrap()
+                .into_inner(),
+            Type::Grass
+        );
+        assert!(serde_json::from_str::<Tagged<Type, Event>>("\"Grass\"").is_err());
+        assert!(serde_json::from_str::<Tagged<Type, Event>>("\"evt:Water\"").is_err());
+    }
+
+    #[test]
+    fn serializes_through_serialize() {
+        let tagged = Tagged::<_, Event>::new(Element::Earth);
+        assert_eq!(
+            serde_json::to_string(&tagged).unwrap(),
+            "\"evt:Element.earth\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Tagged<Element, Event>>("\"evt:Element.earth\"")
+                .unwrap()
+                .into_inner(),
+            Element::Earth
+        );
+        assert!(serde_json::to_string(&Tagged::<_, Event>::new(Numbered::One)).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn pads_prefixed_label() {
+        extern crate std;
+        use std::format;
+
+        let tagged = Tagged::<_, Event>::new(Type::Fire);
+        assert_eq!(format!("{tagged}"), "evt:Fire");
+        assert_eq!(format!("{tagged:10}"), "evt:Fire  ");
+        assert_eq!(format!("{tagged:>10}"), "  evt:Fire");
+        assert_eq!(format!("{tagged:*^11}"), "*evt:Fire**");
+        assert_eq!(format!("{tagged:4}"), "evt:Fire");
+    }
+}
+
+#[no_implicit_prelude]
+mod hygiene {
+    use ::serde_string_enum::{
+        DebugLabeledStringEnum,
+        DeserializeLabeledStringEnum,
+        DeserializeStringEnum,
+        SerializeLabeledStringEnum,
+        SerializeStringEnum,
+    };
+
+    #[derive(
+        ::core::cmp::PartialEq,
+        DebugLabeledStringEnum,
+        SerializeLabeledStringEnum,
+        DeserializeLabeledStringEnum,
+    )]
+    #[string_enum(null = Grass)]
+    enum Type {
+        #[string = "Grass"]
+        Grass,
+        #[string = "Fire"]
+        #[number = 1]
+        Fire,
+        #[string = "Water"]
+        #[string(bool = true)]
+        Water,
+    }
+
+    #[derive(
+        ::core::fmt::Debug,
+        ::core::cmp::PartialEq,
+        SerializeLabeledStringEnum,
+        DeserializeLabeledStringEnum,
+    )]
+    #[string_enum(default = Unknown)]
+    enum Weather {
+        #[string = "Sun"]
+        Sun,
+        #[string = "Unknown"]
+        Unknown,
+    }
+
+    #[derive(
+        ::core::fmt::Debug,
+        ::core::cmp::PartialEq,
+        SerializeLabeledStringEnum,
+        DeserializeLabeledStringEnum,
+    )]
+    enum Label<'a> {
+        #[string = "Fixed"]
+        Fixed,
+        #[string(other)]
+        Other(&'a str),
+    }
+
+    #[derive(
+        ::core::fmt::Debug, ::core::cmp::PartialEq, SerializeStringEnum, DeserializeStringEnum,
+    )]
+    enum Rotation {
+        Left,
+    }
+
+    impl ::core::fmt::Display for Rotation {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.write_str("L")
+        }
+    }
+
+    impl ::core::str::FromStr for Rotation {
+        type Err = ();
+        fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+            match s {
+                "L" => ::core::result::Result::Ok(Self::Left),
+                _ => ::core::result::Result::Err(()),
+            }
+        }
+    }
+
+    #[test]
+    fn expands_without_prelude() {
+        ::core::assert_eq!(::serde_json::from_str::<Type>("true").unwrap(), Type::Water);
+        ::core::assert_eq!(
+            ::serde_json::from_str::<Weather>("\"Rain\"").unwrap(),
+            Weather::Unknown
+        );
+        ::core::assert_eq!(
+            ::serde_json::from_str::<Label>("\"Custom\"").unwrap(),
+            Label::Other("Custom")
+        );
+        ::core::assert_eq!(
+            ::serde_json::from_str::<Rotation>("\"L\"").unwrap(),
+            Rotation::Left
         );
     }
 }
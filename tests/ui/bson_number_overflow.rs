@@ -0,0 +1,17 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(bson, serialize_as = "number")]
+enum Type {
+    #[string = "Grass"]
+    #[number = 1]
+    Grass,
+    #[string = "Fire"]
+    #[number = 9223372036854775808]
+    Fire,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use serde_string_enum::DeserializeLabeledStringEnum;
+
+#[derive(DeserializeLabeledStringEnum)]
+#[string_enum(c_ffi)]
+enum Category {
+    #[string = "Physical"]
+    Physical,
+}
+
+fn main() {}
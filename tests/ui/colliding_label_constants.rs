@@ -0,0 +1,12 @@
+use serde_string_enum::SerializeLabeledStringEnum;
+
+#[derive(SerializeLabeledStringEnum)]
+#[string_enum(label_constants)]
+enum Server {
+    #[string = "web"]
+    HttpServer,
+    #[string = "HTTP"]
+    HTTPServer,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+
+use serde_string_enum::DebugLabeledStringEnum;
+
+#[derive(DebugLabeledStringEnum)]
+#[string_enum(debug_expand)]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use serde_string_enum::SerializeLabeledStringEnum;
+
+#[derive(SerializeLabeledStringEnum)]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+    #[string("Fire", id = 0)]
+    Fire,
+}
+
+fn main() {}
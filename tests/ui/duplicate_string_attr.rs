@@ -0,0 +1,12 @@
+use serde_string_enum::SerializeLabeledStringEnum;
+
+#[derive(SerializeLabeledStringEnum)]
+enum Type {
+    #[string = "Grass"]
+    #[string = "Leaf"]
+    Grass,
+    #[string("Fire", "Flame")]
+    Fire,
+}
+
+fn main() {}
@@ -4,13 +4,6 @@ use serde_string_enum::{
 };
 
 #[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
-enum Type {
-    #[string = "Grass"]
-    Grass,
-    #[string = "Fire"]
-    Fire,
-    #[string = "Water"]
-    Water(u8),
-}
+enum Type {}
 
 fn main() {}
@@ -0,0 +1,12 @@
+use serde_string_enum::DeserializeLabeledStringEnum;
+
+#[derive(DeserializeLabeledStringEnum)]
+enum Type {
+    #[string = ""]
+    None,
+    #[string = "Fire"]
+    #[alias = ""]
+    Fire,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use serde_string_enum::DeserializeLabeledStringEnum;
+
+#[derive(DeserializeLabeledStringEnum)]
+#[string_enum(generate_tests)]
+enum Value<T> {
+    #[string = "None"]
+    None,
+    #[string(other)]
+    Other(T),
+}
+
+#[derive(DeserializeLabeledStringEnum)]
+#[string_enum(generate_tests)]
+enum Code<const N: usize> {
+    #[string = "Ok"]
+    Ok,
+}
+
+fn main() {}
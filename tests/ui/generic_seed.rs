@@ -0,0 +1,12 @@
+use serde_string_enum::DeserializeLabeledStringEnum;
+
+#[derive(DeserializeLabeledStringEnum)]
+#[string_enum(seed)]
+enum Event<'a> {
+    #[string = "Click"]
+    Click,
+    #[string(other)]
+    Other(&'a str),
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use serde_string_enum::DeserializeLabeledStringEnum;
+
+#[derive(DeserializeLabeledStringEnum)]
+#[string_enum(header_name = "x-street")]
+enum Street {
+    #[string = "Straße"]
+    Strasse,
+    #[string = "Main"]
+    #[alias = "main\n"]
+    Main,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(label_policy = "non_empty")]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+    #[string = "Fire "]
+    Fire,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+#![deny(deprecated)]
+
+use core::{fmt, str::FromStr};
+use serde_string_enum::{DeserializeStringEnum, SerializeStringEnum};
+
+#[derive(SerializeStringEnum, DeserializeStringEnum)]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+    #[alias = "Flame"]
+    Fire,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Grass")
+    }
+}
+
+impl FromStr for Type {
+    type Err = ();
+    fn from_str(_: &str) -> Result<Self, ()> {
+        Ok(Type::Fire)
+    }
+}
+
+fn main() {}
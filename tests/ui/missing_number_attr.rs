@@ -0,0 +1,16 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(serialize_as = "number")]
+enum Type {
+    #[string = "Grass"]
+    #[number = 1]
+    Grass,
+    #[string = "Fire"]
+    Fire,
+}
+
+fn main() {}
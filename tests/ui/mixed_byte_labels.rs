@@ -0,0 +1,12 @@
+use serde_string_enum::SerializeLabeledStringEnum;
+
+#[derive(SerializeLabeledStringEnum)]
+#[string_enum(qualified)]
+enum Opcode {
+    #[string = b"GR"]
+    Grass,
+    #[string = "FI"]
+    Fire,
+}
+
+fn main() {}
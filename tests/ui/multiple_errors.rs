@@ -0,0 +1,15 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(unknown_key = 1, serialize_case = "camelCase", inline)]
+enum Type {
+    Grass,
+    #[string = "Fire"]
+    Fire(u8),
+    Water,
+}
+
+fn main() {}
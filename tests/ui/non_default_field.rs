@@ -3,6 +3,8 @@ use serde_string_enum::{
     SerializeLabeledStringEnum,
 };
 
+struct Flow;
+
 #[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
 enum Type {
     #[string = "Grass"]
@@ -10,7 +12,7 @@ enum Type {
     #[string = "Fire"]
     Fire,
     #[string = "Water"]
-    Water(u8),
+    Water(Flow),
 }
 
 fn main() {}
@@ -1,8 +1,13 @@
-use serde_string_enum::{DeserializeStringEnum, SerializeStringEnum};
+use serde_string_enum::{DeserializeStringEnum, SerializeLabeledStringEnum, SerializeStringEnum};
 
 #[derive(SerializeStringEnum, DeserializeStringEnum)]
 struct Type {
     name: String,
 }
 
+#[derive(SerializeLabeledStringEnum)]
+union Value {
+    number: u32,
+}
+
 fn main() {}
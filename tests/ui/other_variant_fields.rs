@@ -0,0 +1,16 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+enum Type<'a> {
+    #[string = "Grass"]
+    Grass,
+    #[string = "Fire"]
+    Fire,
+    #[string(other)]
+    Other(&'a str, u8),
+}
+
+fn main() {}
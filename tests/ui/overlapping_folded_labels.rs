@@ -0,0 +1,12 @@
+use serde_string_enum::DeserializeLabeledStringEnum;
+
+#[derive(DeserializeLabeledStringEnum)]
+#[string_enum(deny_overlap)]
+enum Street {
+    #[string = "straße"]
+    Street,
+    #[string = "STRASSE"]
+    Road,
+}
+
+fn main() {}
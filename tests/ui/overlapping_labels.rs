@@ -0,0 +1,14 @@
+use serde_string_enum::DeserializeLabeledStringEnum;
+
+#[derive(DeserializeLabeledStringEnum)]
+#[string_enum(deny_overlap)]
+enum Type {
+    #[string = "Grass"]
+    #[alias = "Plant"]
+    Grass,
+    #[string = "Bug"]
+    #[alias = "Plant"]
+    Bug,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use serde_string_enum::DeserializeLabeledStringEnum;
+
+#[derive(DeserializeLabeledStringEnum)]
+enum Code<'a> {
+    #[string = "ERR_TIMEOUT"]
+    Timeout,
+    #[string(pattern = "ERR_*")]
+    Error(&'a str),
+    #[string = "WARN"]
+    #[string(pattern = "WARN_*")]
+    Warning(String),
+    #[string(other, priority = 1)]
+    Other(String),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(poem_openapi)]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+    #[string(pattern = "grass-*")]
+    Grassy(String),
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(rkyv)]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+    #[string(other)]
+    Other(String),
+}
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(archive_as = "index")]
+enum Weather {
+    #[string = "Sun"]
+    Sun,
+}
+
+fn main() {}
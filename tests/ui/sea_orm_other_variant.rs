@@ -0,0 +1,15 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(sea_orm)]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+    #[string(other)]
+    Other(String),
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use serde_string_enum::SerializeLabeledStringEnum;
+
+#[derive(SerializeLabeledStringEnum)]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+    #[string = "Fire"]
+    Fire,
+}
+
+#[derive(SerializeLabeledStringEnum)]
+#[string_enum(subset_of = Type)]
+enum StarterType {
+    #[string = "Fire"]
+    Fire,
+    #[string = "Water"]
+    Water,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(default = Unknown)]
+enum Type {
+    #[string = "Grass"]
+    Grass,
+    #[string = "Fire"]
+    Fire,
+    #[string = "Water"]
+    Water,
+}
+
+fn main() {}
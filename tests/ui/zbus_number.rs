@@ -0,0 +1,14 @@
+use serde_string_enum::{
+    DeserializeLabeledStringEnum,
+    SerializeLabeledStringEnum,
+};
+
+#[derive(SerializeLabeledStringEnum, DeserializeLabeledStringEnum)]
+#[string_enum(zbus, serialize_as = "number")]
+enum Type {
+    #[string = "Grass"]
+    #[number = 1]
+    Grass,
+}
+
+fn main() {}